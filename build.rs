@@ -1,7 +1,33 @@
+use std::process::Command;
+
 fn main() {
     #[cfg(windows)]
     {
         let _ = embed_resource::compile("app.rc", embed_resource::NONE);
         println!("cargo:rerun-if-changed=app.rc");
     }
+
+    // Build info for `--version`: the target triple, and the git commit when built from a checkout
+    println!(
+        "cargo:rustc-env=FERRISKEYS_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+    println!("cargo:rerun-if-changed=build.rs");
+    if let Some(hash) = git(&["rev-parse", "--short", "HEAD"]) {
+        println!("cargo:rustc-env=FERRISKEYS_GIT_HASH={hash}");
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed=.git/{head_ref}");
+        }
+    }
+}
+
+/// Runs `git` with `args`, returning its trimmed output if it succeeded.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string()).filter(|t| !t.is_empty())
 }
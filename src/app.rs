@@ -1,38 +1,121 @@
 use crate::{
+    cli::Args,
     config::config::{setup_custom_fonts, Config},
-    input::input::{start_input_listener, InputEvent},
-    ui::visualiser::VisualiserApp,
+    core::wake::{frame_delay, set_waker},
+    error::FerrisKeysError,
+    input::{
+        broadcast::Broadcaster,
+        input::{start_input_listener, InputEvent},
+        layout::{detect_layout, KeyboardLayout},
+        replay::{record_events, replay_events},
+    },
+    ui::{
+        blur::blur_behind,
+        monitor::{detect_monitors, resolve_position},
+        visualiser::VisualiserApp,
+    },
 };
 use eframe::egui::{self, ViewportCommand};
 use std::{
+    path::Path,
     sync::{mpsc, Arc},
     thread,
 };
 
 /// Launches the FerrisKeys visualizer application.
 ///
-/// - Spawns a background thread to listen for keyboard/mouse input events.
+/// - Spawns a background thread to listen for keyboard/mouse input events, or to
+///   replay a recording when `--replay` is given.
+/// - Broadcasts those events so several consumers can each subscribe to the stream.
+/// - Records the stream to a file when `--record` is given.
 /// - Loads the user configuration, including window size, position, and fonts.
-/// - Sets up a transparent, always-on-top window with no decorations.
+/// - Sets up a transparent window, borderless and always on top unless configured otherwise.
 /// - Initializes the `VisualiserApp`, passing in the input event receiver channel.
+/// - With `--preview`, keeps a sample key of every style on screen.
+/// - With `--screenshot`, saves the overlay to a PNG once keys are shown, then exits.
+/// - When `mirror_all_monitors` is set, subscribes one extra overlay per monitor.
 /// - Configures the GUI context, including font overrides and mouse passthrough.
+/// - With `blur_background`, asks the compositor to blur behind the overlay.
+/// - Wakes the UI from the input pipeline whenever an event arrives.
+///
+/// # Arguments
+/// * `args` - Parsed command-line options.
 ///
 /// # Returns
-/// `Ok(())` if the app launches and exits successfully, or `Err(eframe::Error)` if startup fails.
-pub fn run() -> Result<(), eframe::Error> {
+/// `Ok(())` if the app launches and exits successfully, or `Err(FerrisKeysError::Ui)` if
+/// the overlay window fails.
+fn launch(args: &Args) -> Result<(), FerrisKeysError> {
     // Create a channel for transmitting input events between threads
     let (tx, rx) = mpsc::channel::<InputEvent>();
 
-    // Spawn the input listener in a background thread
-    thread::spawn(move || {
-        start_input_listener(tx);
+    // Load configuration from disk (or fallback to defaults)
+    let config = Config::load_auto(args.config.as_deref());
+
+    // Spawn the input listener (or a recording player) in a background thread
+    let replay = args.replay.clone();
+    // Use the configured keyboard layout, or detect the active one
+    let layout = config.layout.unwrap_or_else(|| {
+        detect_layout().unwrap_or_else(|e| {
+            warn!("{e}. Set `layout` in the config to pick one; using the US layout.");
+            KeyboardLayout::UnitedStates
+        })
     });
+    let compose = config.compose;
+    if config.show_layout {
+        println!("Keyboard layout: {layout}");
+    }
 
-    // Load configuration from disk (or fallback to defaults)
-    let config = Config::load_auto();
+    thread::spawn(move || match replay {
+        Some(path) => replay_events(&path, tx),
+        None => start_input_listener(tx, layout, compose),
+    });
+
+    // Resolve monitor-relative coordinates to absolute desktop pixels
+    let position = resolve_position(&config);
+
+    // When mirroring, each extra monitor gets its own overlay offset from the main one
+    let mirror_offsets = if config.mirror_all_monitors {
+        mirror_offsets(position)
+    } else {
+        Vec::new()
+    };
+
+    // Fan input events out so every overlay (and any other subsystem) gets its own copy
+    let broadcaster = Broadcaster::spawn(rx);
+
+    // Save the event stream with timings if requested
+    if let Some(path) = &args.record {
+        record_events(path.clone(), broadcaster.subscribe());
+    }
+
+    let mirrors = mirror_offsets
+        .iter()
+        .map(|offset| {
+            VisualiserApp::mirrored(config.clone(), broadcaster.subscribe(), *offset)
+                .with_layout(layout)
+                .with_preview(args.preview)
+        })
+        .collect();
+
+    // Play a sound on key presses if enabled (requires the `sound` feature)
+    if config.sound.enabled {
+        #[cfg(feature = "sound")]
+        crate::ui::sound::spawn_sound_player(&config.sound, broadcaster.subscribe());
+
+        #[cfg(not(feature = "sound"))]
+        warn!("Sound is enabled in config, but FerrisKeys was built without the `sound` feature.");
+    }
+
+    let mirror_count = mirror_offsets.len();
+    let blur_background = config.blur_background;
+    let mouse_passthrough = config.mouse_passthrough();
 
     // Construct the visualiser app with config and input event receiver
-    let app = VisualiserApp::new(config.clone(), rx);
+    let app = VisualiserApp::new(config.clone(), broadcaster.subscribe())
+        .with_mirrors(mirrors)
+        .with_layout(layout)
+        .with_preview(args.preview)
+        .with_screenshot(args.screenshot.clone());
 
     // Load application icon from embedded PNG byte data
     let icon = eframe::icon_data::from_png_bytes(include_bytes!("../assets/images/FerrisKeys.ico"))
@@ -41,24 +124,141 @@ pub fn run() -> Result<(), eframe::Error> {
     // Define window options including size, position, transparency, etc.
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_decorations(false) // No window borders or titlebar
+            .with_title(&config.window_title) // Window title
+            .with_app_id(&config.app_id) // App id for window-manager rules
+            .with_decorations(config.decorations) // No window borders or titlebar by default
+            .with_resizable(config.resizable) // Allow or prevent user resizing
             .with_transparent(true) // Transparent background
-            .with_always_on_top() // Keep window above others
+            .with_window_level(config.window_level()) // Keep window above others if configured
+            .with_taskbar(!config.skip_taskbar) // Hide from the taskbar and Alt-Tab if configured
+            .with_window_type(config.x11_window_type()) // The same on X11, as a utility window
             .with_inner_size(config.size) // Initial window size
-            .with_position(config.position) // Initial window position
+            .with_position(position) // Initial window position
             .with_icon(Arc::new(icon)), // Window/taskbar icon
         ..Default::default()
     };
 
     // Run the application using `eframe`, setting up the GUI context and app lifecycle
     eframe::run_native(
-        "FerrisKeys",
+        &config.window_title,
         options,
         Box::new(move |cc| {
             setup_custom_fonts(&cc.egui_ctx); // Load user/custom fonts
             cc.egui_ctx
-                .send_viewport_cmd(ViewportCommand::MousePassthrough(true)); // Allow clicks to pass through
+                .send_viewport_cmd(ViewportCommand::MousePassthrough(mouse_passthrough)); // Let clicks through unless the window has borders
+            if blur_background {
+                blur_behind(cc, true); // Blurred backdrop, where the compositor supports it
+            }
+
+            // Repaint every overlay when an event arrives instead of polling for it, and
+            // likewise for tray requests and listener failures. With `max_fps`, the repaint
+            // waits for the rest of the frame, so fast typing still shares frames.
+            let ctx = cc.egui_ctx.clone();
+            let repaint = move || {
+                let delay = frame_delay();
+                ctx.request_repaint_after(delay);
+                for i in 0..mirror_count {
+                    ctx.request_repaint_after_for(delay, VisualiserApp::mirror_viewport_id(i));
+                }
+            };
+            broadcaster.on_event(repaint.clone());
+            set_waker(repaint);
+
             Ok(Box::new(app))
         }),
-    )
+    )?;
+    Ok(())
+}
+
+/// Computes the offset from the main overlay to the same spot on every other monitor.
+///
+/// The monitor containing `position` (or the first one, if none does) is treated as the
+/// home monitor; every other monitor gets an offset equal to the distance between its
+/// top-left corner and the home monitor's.
+fn mirror_offsets(position: [f32; 2]) -> Vec<[f32; 2]> {
+    let monitors = detect_monitors();
+    let Some(home) = monitors
+        .iter()
+        .find(|m| m.contains(position))
+        .or(monitors.first())
+        .copied()
+    else {
+        return Vec::new();
+    };
+
+    monitors
+        .iter()
+        .filter(|m| **m != home)
+        .map(|m| {
+            [
+                m.position[0] - home.position[0],
+                m.position[1] - home.position[1],
+            ]
+        })
+        .collect()
+}
+
+/// Returns the effective config as TOML, for `--dump-config`.
+///
+/// Unlike startup, a config file that can't be read or parsed is an error here, rather
+/// than silently dumping the defaults used in its place.
+fn dump_config(explicit: Option<&Path>) -> Result<String, FerrisKeysError> {
+    let config = match Config::active_path(explicit) {
+        Some(path) if path.exists() => Config::load(&path)?,
+        _ => Config::default(),
+    };
+    Ok(config.to_toml()?)
+}
+
+/// Detects problematic Wayland environments that are known to cause issues with window creation.
+/// Specifically checks for Raspberry Pi setups where Glutin fails under Wayland.
+///
+/// # Returns
+/// `true` if Wayland is in use and it's likely to cause issues.
+/// `false` otherwise.
+fn is_problematic_wayland() -> bool {
+    std::env::var("XDG_SESSION_TYPE").map_or(false, |v| v == "wayland")
+        && std::env::var("WAYLAND_DISPLAY").is_ok()
+}
+
+/// Runs FerrisKeys with parsed command-line options; the `ferriskeys` binary only parses
+/// them and calls this.
+///
+/// - Sets the log level (errors and warnings only, unless asked for more).
+/// - With `--dump-config`, prints the effective config and returns.
+/// - Ensures the user configuration file exists, creating one from defaults if missing.
+/// - On Windows, initializes a system tray icon.
+/// - Stops early if a known problematic Wayland setup is detected.
+/// - Otherwise launches the overlay until its window is closed.
+///
+/// # Errors
+/// Returns an error if `--dump-config` can't read the config, the session is a known
+/// problematic Wayland setup, or the overlay window fails.
+pub fn run(args: &Args) -> Result<(), FerrisKeysError> {
+    if let Some(level) = args.log_level {
+        crate::logging::set_level(level);
+    }
+
+    // Print what the config resolves to, before anything else writes to stdout
+    if args.dump_config {
+        print!("{}", dump_config(args.config.as_deref())?);
+        return Ok(());
+    }
+
+    // Ensure configuration file is present or create it from defaults
+    // A read-only config dir isn't fatal: the defaults are used, just not saved
+    if let Err(err) = Config::ensure_config_exists(args.config.as_deref()) {
+        warn!("Could not create the config file ({err}). Using default settings.");
+    }
+
+    // Spawn the system tray icon on Windows; it is removed when this returns
+    #[cfg(all(feature = "windows-tray", target_os = "windows"))]
+    let _tray_icon = crate::ui::tray::spawn_tray(args.config.as_deref());
+
+    // Check for problematic Wayland setup (e.g., on Raspberry Pi)
+    if is_problematic_wayland() {
+        return Err(FerrisKeysError::UnsupportedWayland);
+    }
+
+    launch(args)
 }
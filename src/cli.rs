@@ -0,0 +1,129 @@
+use crate::logging::Level;
+use std::path::PathBuf;
+
+/// Command-line options for FerrisKeys.
+#[derive(Debug, Default)]
+pub struct Args {
+    /// Use this config file instead of searching the default locations.
+    pub config: Option<PathBuf>,
+    /// Record the input event stream (with timings) to this file.
+    pub record: Option<PathBuf>,
+    /// Replay a recorded event stream from this file instead of listening to real input.
+    pub replay: Option<PathBuf>,
+    /// Show one sample key per category, kept on screen, for previewing styles.
+    pub preview: bool,
+    /// Save the overlay to this PNG file once keys are on screen, then exit.
+    pub screenshot: Option<PathBuf>,
+    /// Print the effective config (after fallbacks) as TOML and exit.
+    pub dump_config: bool,
+    /// How much diagnostic output to print, if not the default.
+    pub log_level: Option<Level>,
+}
+
+/// What the command line asks for; see `Args::parse`.
+#[derive(Debug)]
+pub enum Command {
+    /// Run the overlay with these options.
+    Run(Args),
+    /// Print the `version` line and exit (`-V`/`--version`).
+    Version,
+    /// Print `USAGE` and exit (`-h`/`--help`).
+    Help,
+}
+
+/// Usage text printed for `--help` and on invalid arguments.
+pub const USAGE: &str = "\
+Usage: ferriskeys [OPTIONS]
+
+Options:
+  --config FILE   Use FILE as the config (default: $FERRISKEYS_CONFIG, then the user config dir)
+  --record FILE   Record input events with timings to FILE
+  --replay FILE   Replay events from FILE instead of listening to real input
+  --preview       Keep a sample key of every style on screen, for theming
+  --screenshot FILE
+                  Save the overlay to FILE as a PNG once keys are shown, then exit
+  --dump-config   Print the effective config, with fallbacks filled in, and exit
+  --log-level LEVEL
+                  Diagnostics to print to stderr: off, error, warn (default), info or debug
+  -v, --verbose   Print all diagnostics, same as --log-level debug
+  -V, --version   Print version information
+  -h, --help      Print this help";
+
+/// Version line printed for `--version`, e.g. `ferriskeys 0.3.0 (1a2b3c4) x86_64-unknown-linux-gnu`.
+///
+/// The git commit is only included when the binary was built from a git checkout.
+pub fn version() -> String {
+    let name = env!("CARGO_PKG_NAME");
+    let version = env!("CARGO_PKG_VERSION");
+    let target = env!("FERRISKEYS_TARGET");
+    match option_env!("FERRISKEYS_GIT_HASH") {
+        Some(hash) => format!("{name} {version} ({hash}) {target}"),
+        None => format!("{name} {version} {target}"),
+    }
+}
+
+impl Args {
+    /// Parses the process arguments.
+    ///
+    /// Nothing is printed: `--version` and `--help` are returned as a `Command` for the
+    /// caller to handle.
+    ///
+    /// # Returns
+    /// `Err` with a message describing the problem if an argument is unknown or missing a value.
+    pub fn parse() -> Result<Command, String> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    /// Parses arguments from an iterator (excluding the program name), like `parse`.
+    pub fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--config" => parsed.config = Some(Self::value(&arg, args.next())?),
+                "--record" => parsed.record = Some(Self::value(&arg, args.next())?),
+                "--replay" => parsed.replay = Some(Self::value(&arg, args.next())?),
+                "--preview" => parsed.preview = true,
+                "--screenshot" => parsed.screenshot = Some(Self::value(&arg, args.next())?),
+                "--dump-config" => parsed.dump_config = true,
+                "-v" | "--verbose" => parsed.log_level = Some(Level::Debug),
+                "--log-level" => {
+                    let name = args.next().ok_or("Missing LEVEL after '--log-level'")?;
+                    let level =
+                        Level::parse(&name).ok_or_else(|| format!("Unknown log level '{name}'"))?;
+                    parsed.log_level = Some(level);
+                }
+                "-V" | "--version" => return Ok(Command::Version),
+                "-h" | "--help" => return Ok(Command::Help),
+                other => return Err(format!("Unknown argument '{other}'")),
+            }
+        }
+
+        Ok(Command::Run(parsed))
+    }
+
+    /// Returns the value following a flag, or an error if it is missing.
+    fn value(flag: &str, value: Option<String>) -> Result<PathBuf, String> {
+        value
+            .map(PathBuf::from)
+            .ok_or_else(|| format!("Missing FILE after '{flag}'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        Args::parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn version_and_help_are_returned_instead_of_exiting() {
+        assert!(matches!(parse(&["--preview", "-V"]), Ok(Command::Version)));
+        assert!(matches!(parse(&["--help"]), Ok(Command::Help)));
+        assert!(matches!(parse(&["--preview"]), Ok(Command::Run(args)) if args.preview));
+        assert!(parse(&["--bogus"]).is_err());
+    }
+}
@@ -1,20 +1,39 @@
 use crate::config::default_config;
-use crate::input::keymap::KeyCategory;
+use crate::config::locale;
+use crate::config::theme::Theme;
+use crate::error::FerrisKeysError;
+use crate::input::keymap::{chord_modifier, normalize_key_label, KeyCategory};
+use crate::input::layout::KeyboardLayout;
 
-use eframe::egui::{self, Color32, FontData, FontDefinitions, FontFamily};
+use eframe::egui::{
+    self, Color32, FontData, FontDefinitions, FontFamily, WindowLevel, X11WindowType,
+};
+#[cfg(feature = "hot-reload")]
 use notify::{Config as NotifyConfig, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::Path;
-use std::sync::mpsc::{channel, Receiver};
-use std::thread;
-use std::time::{Duration, SystemTime};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant, SystemTime};
+#[cfg(feature = "hot-reload")]
+use std::{sync::mpsc::channel, thread};
 use toml::Value;
+use toml_edit::DocumentMut;
 /// A visual style definition for a specific key category.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Style {
     /// Width of the key display box.
     pub width: f32,
+    /// If set, boxes at least this wide grow to fit their label (see `max_width`).
+    pub min_width: Option<f32>,
+    /// If set, boxes grow to fit their label up to this width.
+    pub max_width: Option<f32>,
+    /// Whether to size boxes to their label (within `min_width`/`max_width`) instead of `width`.
+    pub auto_width: bool,
     /// Height of the key display box.
     pub height: f32,
     /// Font size used for the icon (e.g., modifier symbol).
@@ -25,6 +44,365 @@ pub struct Style {
     pub bg_color: Color32,
     /// Foreground (text/icon) color.
     pub fg_color: Color32,
+    /// Optional second background color; when set the box is filled with a gradient.
+    pub bg_color2: Option<Color32>,
+    /// Direction of the gradient from `bg_color` to `bg_color2`.
+    pub gradient_direction: GradientDirection,
+    /// Where the icon sits relative to the label.
+    pub icon_position: IconPosition,
+    /// Optional drop shadow painted behind the key box.
+    pub shadow: Option<Shadow>,
+    /// Optional contrasting outline drawn around the icon and label text.
+    pub text_outline: Option<TextOutline>,
+}
+
+impl Style {
+    /// Returns whether boxes in this style are sized to their measured label rather than
+    /// the fixed `width`.
+    pub fn sized_to_label(&self) -> bool {
+        self.auto_width || self.min_width.is_some() || self.max_width.is_some()
+    }
+}
+
+/// Direction a two-color key background gradient runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GradientDirection {
+    /// `bg_color` at the top, `bg_color2` at the bottom (the default).
+    #[default]
+    Vertical,
+    /// `bg_color` on the left, `bg_color2` on the right.
+    Horizontal,
+}
+
+/// Where a key's icon is drawn relative to its label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IconPosition {
+    /// Above the label, in each category's own arrangement (the default).
+    #[default]
+    Above,
+    /// Below the label.
+    Below,
+    /// Left of the label, on one line.
+    Left,
+    /// Right of the label, on one line.
+    Right,
+}
+
+/// A drop shadow drawn behind a key box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    /// Offset of the shadow from the key box, in pixels.
+    pub offset: [f32; 2],
+    /// How far the shadow spreads beyond the box, faked with layered translucent rects.
+    pub blur: f32,
+    /// Shadow color, including opacity.
+    pub color: Color32,
+}
+
+/// A contrasting outline drawn around key text, for readability over bright backgrounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextOutline {
+    /// Thickness of the outline, in pixels.
+    pub width: f32,
+    /// Outline color, including opacity.
+    pub color: Color32,
+}
+
+/// A rounded panel drawn behind the visible keys, like a dock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Panel {
+    /// Panel color, including its own opacity (independent of the keys').
+    pub color: Color32,
+    /// Space kept between the keys and the panel's top and bottom edges, in pixels.
+    pub padding: f32,
+    /// Space between the panel's left/right edges and the first/last key, in pixels.
+    ///
+    /// Keys are moved inwards by this much so the panel stays inside the overlay.
+    pub content_padding: f32,
+    /// Corner radius of the panel, in pixels.
+    pub rounding: f32,
+}
+
+/// How modifier keys (Shift, Ctrl, Alt, ...) are labelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModifierStyle {
+    /// Only the symbol, e.g. `⇧`. Needs a font with the glyph.
+    Glyph,
+    /// Only the plain word, e.g. `Shift`. Works with any font.
+    Word,
+    /// Symbol and lowercase word, e.g. `⇧ shift` (the default).
+    #[default]
+    Both,
+}
+
+/// When the overlay shows keys at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// Show every key (the default).
+    #[default]
+    Always,
+    /// Show keys only while Shift, Ctrl, Alt or Meta is held, clearing on release.
+    ModifierHeld,
+}
+
+/// What is shown for a key whose label is empty or only whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyLabel {
+    /// Don't show the key at all.
+    Skip,
+    /// Show a `?` box, so the press is still visible (the default).
+    #[default]
+    Placeholder,
+}
+
+/// How keys pressed at nearly the same moment are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortSimultaneous {
+    /// In the order their events arrived (the default).
+    #[default]
+    Time,
+    /// Modifiers first (Ctrl, Shift, Alt, Meta), then the other keys, so shortcuts read
+    /// like `Ctrl+Shift+P`.
+    ModifierFirst,
+}
+
+/// How input is shown on the overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// One box per key or chord (the default).
+    #[default]
+    Keys,
+    /// A caption bar showing the typed text, like live subtitles.
+    Caption,
+}
+
+/// How eagerly the overlay repaints, trading smoothness against CPU use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderMode {
+    /// Repaint steadily while anything is shown, and poll for config changes when idle
+    /// (the default).
+    #[default]
+    Continuous,
+    /// Repaint only for input and running animations; sleep entirely when idle.
+    Reactive,
+}
+
+/// Vertical alignment of key boxes of different heights within a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowAlign {
+    /// Align the tops of all boxes.
+    Top,
+    /// Center every box on the row's midline.
+    Center,
+    /// Sit every box on a common baseline (the default).
+    #[default]
+    Bottom,
+}
+
+/// What to do with keys once the row is wider than the overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overflow {
+    /// Drop the oldest keys that don't fit (the default).
+    #[default]
+    Trim,
+    /// Keep every key and slide the row left so the newest stay visible.
+    Scroll,
+}
+
+/// How a key box animates in when it appears, or out when it expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Animation {
+    /// No animation.
+    None,
+    /// Grow from slightly smaller to full size (the default entry animation).
+    #[default]
+    Scale,
+    /// Fade in or out.
+    Fade,
+    /// Slide up into place, or down out of it, while fading.
+    Slide,
+    /// Grow with a brief overshoot past full size.
+    Pop,
+}
+
+impl Animation {
+    /// Parses an animation name, warning about and falling back to `default` if unknown.
+    fn parse(key: &str, value: &str, default: Animation) -> Animation {
+        match value.to_ascii_lowercase().as_str() {
+            "none" => Animation::None,
+            "scale" => Animation::Scale,
+            "fade" => Animation::Fade,
+            "slide" => Animation::Slide,
+            "pop" => Animation::Pop,
+            other => {
+                warn!("Invalid {} '{}'. Using {:?}.", key, other, default);
+                default
+            }
+        }
+    }
+}
+
+/// A key combination such as `ctrl+shift+k`, matched against live key presses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkey {
+    /// Modifier names that must be held (`shift`, `control`, `alt`, `meta`), sorted.
+    pub modifiers: Vec<&'static str>,
+    /// The non-modifier key, lowercase.
+    pub key: String,
+}
+
+impl Hotkey {
+    /// Parses a `+`-separated combination; the one non-modifier part is the key.
+    pub fn parse(text: &str) -> Option<Hotkey> {
+        let mut modifiers = Vec::new();
+        let mut key = None;
+
+        for part in text.split('+').map(str::trim) {
+            match chord_modifier(part) {
+                Some(name) => modifiers.push(name),
+                None if key.is_none() && !part.is_empty() => key = Some(part.to_lowercase()),
+                None => return None,
+            }
+        }
+
+        modifiers.sort_unstable();
+        modifiers.dedup();
+        Some(Hotkey {
+            modifiers,
+            key: key?,
+        })
+    }
+
+    /// Returns `true` if pressing `label` while exactly `held` modifiers are down triggers it.
+    pub fn matches(&self, held: &[&'static str], label: &str) -> bool {
+        let mut held = held.to_vec();
+        held.sort_unstable();
+
+        let word = normalize_key_label(label)
+            .rsplit(' ')
+            .next()
+            .unwrap_or(label)
+            .to_lowercase();
+        held == self.modifiers && word == self.key
+    }
+}
+
+impl fmt::Display for Hotkey {
+    /// Writes the combination as `Hotkey::parse` reads it, e.g. `control+shift+k`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{modifier}+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl Serialize for Hotkey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// How the configured window `position` is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionOrigin {
+    /// Absolute desktop pixels (the default).
+    Desktop,
+    /// Pixels relative to the top-left corner of the selected `monitor`.
+    Monitor,
+}
+
+/// A modifier key, held to drag the overlay with `grab_modifier`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+    /// The Super, Windows or Command key.
+    Meta,
+}
+
+impl Modifier {
+    /// Parses a modifier name, ignoring case: `shift`, `ctrl`/`control`, `alt`, or
+    /// `meta` (also `super`, `win`, `cmd` or `command`).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "super" | "win" | "cmd" | "command" => Some(Modifier::Meta),
+            other => match chord_modifier(other)? {
+                "shift" => Some(Modifier::Shift),
+                "control" => Some(Modifier::Control),
+                "alt" => Some(Modifier::Alt),
+                "meta" => Some(Modifier::Meta),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the name `ChordFilter::held` lists this modifier by, e.g. `control`.
+    pub fn chord_name(self) -> &'static str {
+        match self {
+            Modifier::Shift => "shift",
+            Modifier::Control => "control",
+            Modifier::Alt => "alt",
+            Modifier::Meta => "meta",
+        }
+    }
+}
+
+/// Screen edge or corner the overlay window is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Anchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the horizontal and vertical alignment as fractions (0.0 = left/top,
+    /// 0.5 = center, 1.0 = right/bottom).
+    pub fn fractions(self) -> (f32, f32) {
+        use Anchor::*;
+        match self {
+            TopLeft => (0.0, 0.0),
+            TopCenter => (0.5, 0.0),
+            TopRight => (1.0, 0.0),
+            CenterLeft => (0.0, 0.5),
+            Center => (0.5, 0.5),
+            CenterRight => (1.0, 0.5),
+            BottomLeft => (0.0, 1.0),
+            BottomCenter => (0.5, 1.0),
+            BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// Settings for optional keypress sound feedback (`[sound]` table).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SoundConfig {
+    /// Whether to play a sound on each key press.
+    pub enabled: bool,
+    /// Path to the sample to play (WAV or Ogg Vorbis).
+    pub file: String,
 }
 
 /// Runtime configuration for FerrisKeys, loaded from `config.toml`.
@@ -34,14 +412,149 @@ pub struct Config {
     pub styles: HashMap<KeyCategory, Style>,
     /// Timeout for showing key presses (in milliseconds).
     pub timeout_ms: u64,
+    /// Maximum gap between two left clicks for them to count as a double-click (in milliseconds).
+    pub double_click_ms: u64,
+    /// How long a repeat of the same key is suppressed after it is shown (in milliseconds).
+    pub debounce_ms: u64,
+    /// Whether to show a drag indicator while a mouse button is held and the mouse moves.
+    pub show_mouse_drag: bool,
+    /// Show keys that match no category (e.g. `Unknown(123)`); `false` drops them as noise.
+    pub show_unknown: bool,
+    /// Show numpad keys lit up in a fixed numpad-shaped grid instead of the key row.
+    pub show_numpad_grid: bool,
+    /// Append keys in the `Unknown` category to `unmapped.log` next to the config, once each.
+    pub log_unmapped: bool,
+    /// Keep the last chord on screen until the next press instead of expiring it.
+    pub sticky: bool,
+    /// Hide Shift/Ctrl/Alt/Meta pressed on their own; show them only together with another key.
+    pub modifiers_only_in_chords: bool,
+    /// When keys are shown: always, or only while a modifier is held.
+    pub trigger: Trigger,
+    /// How keys pressed at nearly the same moment are ordered in the row.
+    pub sort_simultaneous: SortSimultaneous,
+    /// What to show for keys whose label is empty or only whitespace.
+    pub empty_label: EmptyLabel,
+    /// Whether input is shown as key boxes or as a caption of the typed text.
+    pub display_mode: DisplayMode,
+    /// Show input only while the focused window's class or title contains one of these; empty shows it everywhere.
+    pub only_when_focused: Vec<String>,
+    /// Whether a small dot in the top-right corner shows whether input is being captured.
+    pub show_capture_indicator: bool,
+    /// Join letters, digits, symbols and spaces typed in quick succession into one entry.
+    pub group_text: bool,
+    /// Longest pause between typed keys that still continues a `group_text` run.
+    pub group_text_ms: u64,
+    /// Key combination that wipes all displayed keys, e.g. `ctrl+shift+k`.
+    pub clear_hotkey: Option<Hotkey>,
+    /// Built-in theme names that `theme_cycle_hotkey` steps through, e.g. `["dark", "light"]`.
+    pub theme_cycle: Vec<String>,
+    /// Key combination that applies the next theme in `theme_cycle`.
+    pub theme_cycle_hotkey: Option<Hotkey>,
+    /// Key combination that saves the overlay as a PNG next to the config file.
+    pub screenshot_hotkey: Option<Hotkey>,
+    /// Key combination that opens the settings window.
+    pub settings_hotkey: Option<Hotkey>,
+    /// Number of fixed positional slots for keys; `0` packs keys and collapses gaps.
+    pub fixed_slots: usize,
+    /// Upper bound on repaints per second, including while events arrive; `0` means uncapped.
+    pub max_fps: u64,
+    /// When to repaint: steadily while keys are shown, or only as much as needed.
+    pub render_mode: RenderMode,
+    /// Longest time one frame may advance animations by, so a slow frame doesn't make keys
+    /// jump; `0` means no limit.
+    pub max_animation_step_ms: u64,
+    /// Minimum time between config hot-reloads, so editors that save on every keystroke don't make the overlay flicker.
+    pub reload_interval_ms: u64,
+    /// Whether edits to the config file are applied while running.
+    pub hot_reload: bool,
+    /// Also poll the config file's contents for changes, for filesystems where mtime is coarse or unreliable.
+    pub watch_content: bool,
+    /// How modifier keys are labelled: glyph only, word only, or both.
+    pub modifier_style: ModifierStyle,
+    /// Whether to draw Nerd Font/Unicode icons; `false` swaps them for plain words.
+    pub glyphs: bool,
+    /// Keyboard layout to use instead of auto-detection (e.g. `"us"`, `"gb"`, `"es"`).
+    pub layout: Option<KeyboardLayout>,
+    /// Show the keyboard layout in use in the overlay corner, for diagnosing wrong symbols.
+    pub show_layout: bool,
+    /// Show Compose sequences (e.g. `Compose`, `'`, `e`) as the character they produce (Linux).
+    pub compose: bool,
+    /// Draw icon and label together on one centered line, allowing shorter boxes.
+    pub compact: bool,
+    /// Round key boxes and text to whole pixels for crisper edges on low-DPI screens.
+    pub pixel_snap: bool,
+    /// Vertical alignment of mixed-height boxes against the tallest one in the row.
+    pub row_align: RowAlign,
+    /// What happens to keys that no longer fit: trimmed, or kept and scrolled.
+    pub overflow: Overflow,
+    /// How new keys appear.
+    pub entry_animation: Animation,
+    /// How expired keys disappear.
+    pub exit_animation: Animation,
+    /// Whether to accent the newest key with `highlight_color`.
+    pub highlight_latest: bool,
+    /// Tint and border color for the newest key.
+    pub highlight_color: Color32,
+    /// Border width around the newest key, in pixels.
+    pub highlight_border: f32,
+    /// Optional rounded panel drawn behind the row of keys.
+    pub panel: Option<Panel>,
+    /// Whether keys that are still held down pulse, easing to steady once released.
+    pub pulse_held: bool,
+    /// Whether keys pressed in quick succession are drawn brighter and slightly larger.
+    pub velocity_visualization: bool,
     /// Position of the overlay window (x, y).
     pub position: [f32; 2],
+    /// Whether `position` is relative to the desktop or to the selected monitor.
+    pub position_relative_to: PositionOrigin,
+    /// Index of the monitor used for monitor-relative positioning and anchoring.
+    pub monitor: usize,
+    /// Screen edge/corner to anchor the window to; ignored when `position` is set.
+    pub anchor: Option<Anchor>,
+    /// Distance kept from the screen edges when anchoring, and from the cursor with
+    /// `follow_cursor` (horizontal, vertical; in pixels).
+    pub margin: [f32; 2],
     /// Size of the overlay window (width, height).
     pub size: [f32; 2],
+    /// Cap on the width of the key row, however wide the window is (0 = no cap).
+    pub max_content_width: f32,
+    /// Cap on the height of the key row, however tall the window is (0 = no cap).
+    pub max_content_height: f32,
+    /// Whether to mirror the overlay onto every connected monitor.
+    pub mirror_all_monitors: bool,
+    /// Whether the overlay moves to sit just below-right of the mouse cursor.
+    pub follow_cursor: bool,
+    /// Whether the overlay window stays above other windows.
+    pub always_on_top: bool,
+    /// Whether to keep the overlay out of the taskbar and Alt-Tab (on Windows and X11).
+    pub skip_taskbar: bool,
+    /// Whether the window has a title bar and borders.
+    pub decorations: bool,
+    /// Whether the window can be resized by the user.
+    pub resizable: bool,
+    /// Modifier that makes the overlay grabbable while held.
+    pub grab_modifier: Option<Modifier>,
+    /// Ask the compositor to blur whatever is behind the overlay, where supported.
+    pub blur_background: bool,
+    /// Title of the overlay window.
+    pub window_title: String,
+    /// Wayland/X11 application id, used by window managers for window rules.
+    pub app_id: String,
+    /// Keypress sound feedback settings.
+    pub sound: SoundConfig,
+    /// Display text overrides keyed by key name (`[labels]` table), e.g. `Space = "⎵"`.
+    pub labels: HashMap<String, String>,
+    /// Translated key names (`[locale]` table), keyed by the word shown, in both lowercase and
+    /// capitalized form (`shift` and `Shift`), since labels use either.
+    pub locale: HashMap<String, String>,
     /// Path to the loaded configuration file.
-    pub path: String,
+    pub path: PathBuf,
     /// Timestamp of last modification to the config file.
     pub last_modified: Option<SystemTime>,
+    /// Hash of the config file's contents when it was loaded.
+    pub content_hash: Option<u64>,
+    /// When the config was last hot-reloaded, for throttling reloads.
+    pub last_reload: Option<Instant>,
     /// Optional file watcher event channel for hot-reloading.
     #[allow(clippy::type_complexity)]
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -53,36 +566,271 @@ impl Clone for Config {
         Self {
             styles: self.styles.clone(),
             timeout_ms: self.timeout_ms,
+            double_click_ms: self.double_click_ms,
+            debounce_ms: self.debounce_ms,
+            show_mouse_drag: self.show_mouse_drag,
+            show_unknown: self.show_unknown,
+            show_numpad_grid: self.show_numpad_grid,
+            log_unmapped: self.log_unmapped,
+            sticky: self.sticky,
+            modifiers_only_in_chords: self.modifiers_only_in_chords,
+            trigger: self.trigger,
+            sort_simultaneous: self.sort_simultaneous,
+            empty_label: self.empty_label,
+            display_mode: self.display_mode,
+            only_when_focused: self.only_when_focused.clone(),
+            show_capture_indicator: self.show_capture_indicator,
+            group_text: self.group_text,
+            group_text_ms: self.group_text_ms,
+            clear_hotkey: self.clear_hotkey.clone(),
+            theme_cycle: self.theme_cycle.clone(),
+            theme_cycle_hotkey: self.theme_cycle_hotkey.clone(),
+            screenshot_hotkey: self.screenshot_hotkey.clone(),
+            settings_hotkey: self.settings_hotkey.clone(),
+            fixed_slots: self.fixed_slots,
+            max_fps: self.max_fps,
+            render_mode: self.render_mode,
+            max_animation_step_ms: self.max_animation_step_ms,
+            reload_interval_ms: self.reload_interval_ms,
+            hot_reload: self.hot_reload,
+            watch_content: self.watch_content,
+            modifier_style: self.modifier_style,
+            glyphs: self.glyphs,
+            layout: self.layout,
+            show_layout: self.show_layout,
+            compose: self.compose,
+            compact: self.compact,
+            pixel_snap: self.pixel_snap,
+            row_align: self.row_align,
+            overflow: self.overflow,
+            entry_animation: self.entry_animation,
+            exit_animation: self.exit_animation,
+            highlight_latest: self.highlight_latest,
+            highlight_color: self.highlight_color,
+            highlight_border: self.highlight_border,
+            panel: self.panel,
+            pulse_held: self.pulse_held,
+            velocity_visualization: self.velocity_visualization,
             position: self.position,
+            position_relative_to: self.position_relative_to,
+            monitor: self.monitor,
+            anchor: self.anchor,
+            margin: self.margin,
             size: self.size,
+            max_content_width: self.max_content_width,
+            max_content_height: self.max_content_height,
+            mirror_all_monitors: self.mirror_all_monitors,
+            follow_cursor: self.follow_cursor,
+            always_on_top: self.always_on_top,
+            skip_taskbar: self.skip_taskbar,
+            decorations: self.decorations,
+            resizable: self.resizable,
+            grab_modifier: self.grab_modifier,
+            blur_background: self.blur_background,
+            window_title: self.window_title.clone(),
+            app_id: self.app_id.clone(),
+            sound: self.sound.clone(),
+            labels: self.labels.clone(),
+            locale: self.locale.clone(),
             path: self.path.clone(),
             last_modified: self.last_modified,
+            content_hash: self.content_hash,
+            last_reload: self.last_reload,
             reload_rx: None, // cloned configs do not inherit watchers
         }
     }
 }
 
+/// Configs are equal when every setting matches. Where they were loaded from and their
+/// reload state are ignored.
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        // Destructured so a new field can't be left out of the comparison
+        let Config {
+            styles,
+            timeout_ms,
+            double_click_ms,
+            debounce_ms,
+            show_mouse_drag,
+            show_unknown,
+            show_numpad_grid,
+            log_unmapped,
+            sticky,
+            modifiers_only_in_chords,
+            trigger,
+            sort_simultaneous,
+            empty_label,
+            display_mode,
+            only_when_focused,
+            show_capture_indicator,
+            group_text,
+            group_text_ms,
+            clear_hotkey,
+            theme_cycle,
+            theme_cycle_hotkey,
+            screenshot_hotkey,
+            settings_hotkey,
+            fixed_slots,
+            max_fps,
+            render_mode,
+            max_animation_step_ms,
+            reload_interval_ms,
+            hot_reload,
+            watch_content,
+            modifier_style,
+            glyphs,
+            layout,
+            show_layout,
+            compose,
+            compact,
+            pixel_snap,
+            row_align,
+            overflow,
+            entry_animation,
+            exit_animation,
+            highlight_latest,
+            highlight_color,
+            highlight_border,
+            panel,
+            pulse_held,
+            velocity_visualization,
+            position,
+            position_relative_to,
+            monitor,
+            anchor,
+            margin,
+            size,
+            max_content_width,
+            max_content_height,
+            mirror_all_monitors,
+            follow_cursor,
+            always_on_top,
+            skip_taskbar,
+            decorations,
+            resizable,
+            grab_modifier,
+            blur_background,
+            window_title,
+            app_id,
+            sound,
+            labels,
+            locale,
+            path: _,
+            last_modified: _,
+            content_hash: _,
+            last_reload: _,
+            reload_rx: _,
+        } = self;
+        *styles == other.styles
+            && *timeout_ms == other.timeout_ms
+            && *double_click_ms == other.double_click_ms
+            && *debounce_ms == other.debounce_ms
+            && *show_mouse_drag == other.show_mouse_drag
+            && *show_unknown == other.show_unknown
+            && *show_numpad_grid == other.show_numpad_grid
+            && *log_unmapped == other.log_unmapped
+            && *sticky == other.sticky
+            && *modifiers_only_in_chords == other.modifiers_only_in_chords
+            && *trigger == other.trigger
+            && *sort_simultaneous == other.sort_simultaneous
+            && *empty_label == other.empty_label
+            && *display_mode == other.display_mode
+            && *only_when_focused == other.only_when_focused
+            && *show_capture_indicator == other.show_capture_indicator
+            && *group_text == other.group_text
+            && *group_text_ms == other.group_text_ms
+            && *clear_hotkey == other.clear_hotkey
+            && *theme_cycle == other.theme_cycle
+            && *theme_cycle_hotkey == other.theme_cycle_hotkey
+            && *screenshot_hotkey == other.screenshot_hotkey
+            && *settings_hotkey == other.settings_hotkey
+            && *fixed_slots == other.fixed_slots
+            && *max_fps == other.max_fps
+            && *render_mode == other.render_mode
+            && *max_animation_step_ms == other.max_animation_step_ms
+            && *reload_interval_ms == other.reload_interval_ms
+            && *hot_reload == other.hot_reload
+            && *watch_content == other.watch_content
+            && *modifier_style == other.modifier_style
+            && *glyphs == other.glyphs
+            && *layout == other.layout
+            && *show_layout == other.show_layout
+            && *compose == other.compose
+            && *compact == other.compact
+            && *pixel_snap == other.pixel_snap
+            && *row_align == other.row_align
+            && *overflow == other.overflow
+            && *entry_animation == other.entry_animation
+            && *exit_animation == other.exit_animation
+            && *highlight_latest == other.highlight_latest
+            && *highlight_color == other.highlight_color
+            && *highlight_border == other.highlight_border
+            && *panel == other.panel
+            && *pulse_held == other.pulse_held
+            && *velocity_visualization == other.velocity_visualization
+            && *position == other.position
+            && *position_relative_to == other.position_relative_to
+            && *monitor == other.monitor
+            && *anchor == other.anchor
+            && *margin == other.margin
+            && *size == other.size
+            && *max_content_width == other.max_content_width
+            && *max_content_height == other.max_content_height
+            && *mirror_all_monitors == other.mirror_all_monitors
+            && *follow_cursor == other.follow_cursor
+            && *always_on_top == other.always_on_top
+            && *skip_taskbar == other.skip_taskbar
+            && *decorations == other.decorations
+            && *resizable == other.resizable
+            && *grab_modifier == other.grab_modifier
+            && *blur_background == other.blur_background
+            && *window_title == other.window_title
+            && *app_id == other.app_id
+            && *sound == other.sound
+            && *labels == other.labels
+            && *locale == other.locale
+    }
+}
+
+impl Default for Config {
+    /// Returns the default settings, the same as `in_memory`.
+    fn default() -> Self {
+        Config::in_memory()
+    }
+}
+
 impl Config {
     /// Ensures that a valid configuration file exists, creating one from defaults if missing.
-    pub fn ensure_config_exists() -> std::io::Result<()> {
-        let paths = Config::config_paths();
+    ///
+    /// `explicit` is a path given with `--config`, which takes precedence (see `config_paths`).
+    ///
+    /// # Errors
+    /// `FerrisKeysError::Io` if the file or its folder can't be created, e.g. because the
+    /// config dir is read-only. FerrisKeys still runs then, with the default settings.
+    pub fn ensure_config_exists(explicit: Option<&Path>) -> Result<(), FerrisKeysError> {
+        let paths = Config::config_paths(explicit);
 
-        println!("🔍 Checking config paths:");
+        debug!("Checking config paths:");
         for p in &paths {
-            println!("  - {}", p.display());
+            debug!("  - {}", p.display());
         }
 
-        let path = paths.iter().find(|p| p.to_str().is_some()).unwrap();
+        // The same file `load_auto` and the tray use, even for a non-UTF-8 `--config` path
+        let Some(path) = Config::active_path(explicit) else {
+            warn!("No usable config location found. Using default settings.");
+            return Ok(());
+        };
 
         if !path.exists() {
             if let Some(dir) = path.parent() {
-                std::fs::create_dir_all(dir)?;
+                std::fs::create_dir_all(dir).map_err(|e| FerrisKeysError::io(dir, e))?;
             }
 
-            std::fs::write(path, default_config::DEFAULT_CONFIG_TOML)?;
-            println!("Created config at: {}", path.display());
+            std::fs::write(&path, default_config::DEFAULT_CONFIG_TOML)
+                .map_err(|e| FerrisKeysError::io(&path, e))?;
+            info!("Created config at: {}", path.display());
         } else {
-            println!("Config already exists at: {}", path.display());
+            debug!("Config already exists at: {}", path.display());
         }
 
         Ok(())
@@ -90,30 +838,41 @@ impl Config {
 
     /// Loads the first available config file from preferred paths.
     /// If none exist, returns default settings.
-    pub fn load_auto() -> Self {
-        let paths = Config::config_paths();
-
-        for path in &paths {
-            if path.exists() {
-                return Config::load(path.to_str().unwrap());
-            }
+    pub fn load_auto(explicit: Option<&Path>) -> Self {
+        match Config::active_path(explicit) {
+            Some(path) if path.exists() => Config::load(&path).unwrap_or_else(|e| {
+                warn!("{e}. Using default settings.");
+                Config::default()
+            }),
+            _ => Config::default(),
         }
+    }
 
-        Config::default()
+    /// Returns the config file in use: the first of `config_paths` that exists, or else the
+    /// preferred location. The tray's "Open Config" opens its folder, so both always agree.
+    pub fn active_path(explicit: Option<&Path>) -> Option<PathBuf> {
+        let paths = Config::config_paths(explicit);
+        paths.iter().find(|p| p.exists()).or(paths.first()).cloned()
     }
 
-    /// Returns a list of locations to look for `config.toml`.
-    /// - System-specific config dir (e.g., `$HOME/.config/ferriskeys`)
+    /// Returns a list of locations to look for `config.toml`, in order of preference.
+    /// - `explicit`, the path given with `--config`
+    /// - The `FERRISKEYS_CONFIG` environment variable, e.g. for systemd units
+    /// - System-specific config dir (e.g., `$XDG_CONFIG_HOME/ferriskeys`)
     /// - Fallback to current directory
-    fn config_paths() -> Vec<std::path::PathBuf> {
+    fn config_paths(explicit: Option<&Path>) -> Vec<PathBuf> {
         let mut paths = vec![];
 
-        if cfg!(target_os = "windows") {
-            if let Some(appdata) = std::env::var_os("APPDATA") {
-                paths.push(Path::new(&appdata).join("ferriskeys").join("config.toml"));
-            }
-        } else if let Some(home) = std::env::var_os("HOME") {
-            paths.push(Path::new(&home).join(".config/ferriskeys/config.toml"));
+        if let Some(path) = explicit {
+            paths.push(path.to_path_buf());
+        }
+        if let Some(path) = std::env::var_os("FERRISKEYS_CONFIG").filter(|p| !p.is_empty()) {
+            paths.push(path.into());
+        }
+
+        // `%APPDATA%` on Windows; `$XDG_CONFIG_HOME`, or `$HOME/.config` when unset, on Linux
+        if let Some(dir) = dirs::config_dir() {
+            paths.push(dir.join("ferriskeys").join("config.toml"));
         }
 
         if let Ok(cwd) = std::env::current_dir() {
@@ -124,73 +883,628 @@ impl Config {
     }
 
     /// Loads a configuration file from the given path and parses styles, size, position, etc.
-    pub fn load(path: &str) -> Self {
+    ///
+    /// A missing file gives the defaults. Invalid values fall back to their defaults with a
+    /// warning, but a file that can't be read or isn't valid TOML is an error.
+    pub fn load(path: &Path) -> Result<Self, FerrisKeysError> {
+        let last_modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => Some(content),
+            // No config file just means the defaults
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(FerrisKeysError::io(path, e)),
+        };
+        let toml = content
+            .as_deref()
+            .map(str::parse::<Value>)
+            .transpose()
+            .map_err(|source| FerrisKeysError::ConfigParse {
+                path: path.into(),
+                source,
+            })?;
+
+        let mut config = Config::from_toml(toml.as_ref(), path);
+        config.last_modified = last_modified;
+        config.content_hash = content.as_deref().map(hash_content);
+        if config.hot_reload {
+            config.setup_watcher();
+        }
+        Ok(config)
+    }
+
+    /// Builds the default settings without reading any file or watching for changes.
+    ///
+    /// For running without a config file at all, e.g. in tests, CI or an app embedding the
+    /// overlay. Hot-reload is off and `save` has nowhere to write, so changes stay in memory;
+    /// `unmapped.log` and screenshots, normally written next to the config, are skipped too.
+    pub fn in_memory() -> Self {
+        let mut config = Config::from_toml(None, Path::new(""));
+        config.hot_reload = false;
+        config
+    }
+
+    /// Returns the config file the settings are saved to and reloaded from, or `None` for
+    /// an `in_memory` config, which has no file (or folder) to write to.
+    pub fn file(&self) -> Option<&Path> {
+        (!self.path.as_os_str().is_empty()).then_some(self.path.as_path())
+    }
+
+    /// Builds the settings from a parsed config file (or the defaults without one), to be
+    /// saved to and reloaded from `path`.
+    fn from_toml(toml: Option<&Value>, path: &Path) -> Self {
         let mut styles = Self::fallback_styles();
         let mut timeout_ms = 1200;
+        let mut double_click_ms = 400;
+        let mut debounce_ms = 250;
+        let mut show_mouse_drag = false;
+        let mut show_unknown = true;
+        let mut show_numpad_grid = false;
+        let mut log_unmapped = false;
+        let mut sticky = false;
+        let mut modifiers_only_in_chords = false;
+        let mut trigger = Trigger::Always;
+        let mut sort_simultaneous = SortSimultaneous::Time;
+        let mut empty_label = EmptyLabel::Placeholder;
+        let mut display_mode = DisplayMode::Keys;
+        let mut only_when_focused = Vec::new();
+        let mut show_capture_indicator = false;
+        let mut group_text = false;
+        let mut group_text_ms = 600;
+        let mut clear_hotkey = None;
+        let mut theme_cycle = Vec::new();
+        let mut theme_cycle_hotkey = None;
+        let mut screenshot_hotkey = None;
+        let mut settings_hotkey = None;
+        let mut fixed_slots = 0;
+        let mut max_fps = 0;
+        let mut render_mode = RenderMode::Continuous;
+        let mut max_animation_step_ms = 50;
+        let mut reload_interval_ms = 500;
+        let mut hot_reload = true;
+        let mut watch_content = false;
+        let mut modifier_style = ModifierStyle::Both;
+        let mut glyphs = true;
+        let mut layout = None;
+        let mut show_layout = false;
+        let mut compose = false;
+        let mut compact = false;
+        let mut pixel_snap = false;
+        let mut row_align = RowAlign::Bottom;
+        let mut overflow = Overflow::Trim;
+        let mut entry_animation = Animation::Scale;
+        let mut exit_animation = Animation::None;
+        let mut highlight_latest = false;
+        let mut highlight_color = hex("#ffcc00");
+        let mut highlight_border = 2.0;
+        let mut panel = None;
+        let mut pulse_held = false;
+        let mut velocity_visualization = false;
         let mut position = [500.0, 500.0];
+        let mut position_relative_to = PositionOrigin::Desktop;
+        let mut monitor = 0;
+        let mut anchor = None;
+        let mut margin = [16.0, 16.0];
         let mut size = [800.0, 120.0];
-        let path_obj = Path::new(path);
-        let last_modified = fs::metadata(path_obj).and_then(|m| m.modified()).ok();
-
-        if let Ok(content) = fs::read_to_string(path_obj) {
-            if let Ok(toml) = content.parse::<Value>() {
-                if let Some(win) = toml.get("window") {
-                    if let Some(arr) = win.get("position").and_then(|v| v.as_array()) {
-                        if arr.len() == 2 {
-                            position = [
-                                arr[0].as_float().unwrap_or(100.0) as f32,
-                                arr[1].as_float().unwrap_or(100.0) as f32,
-                            ];
-                        }
+        let mut max_content_width = 0.0;
+        let mut max_content_height = 0.0;
+        let mut mirror_all_monitors = false;
+        let mut follow_cursor = false;
+        let mut always_on_top = true;
+        let mut skip_taskbar = true;
+        let mut decorations = false;
+        let mut resizable = false;
+        let mut grab_modifier = None;
+        let mut blur_background = false;
+        let mut window_title = "FerrisKeys".to_string();
+        let mut app_id = "ferriskeys".to_string();
+        let mut sound = SoundConfig::default();
+        let mut labels = HashMap::new();
+        let mut locale = HashMap::new();
+        if let Some(toml) = toml {
+            if let Some(win) = toml.get("window") {
+                if let Some(arr) = win.get("position").and_then(|v| v.as_array()) {
+                    if arr.len() == 2 {
+                        position = [
+                            arr[0].as_float().unwrap_or(100.0) as f32,
+                            arr[1].as_float().unwrap_or(100.0) as f32,
+                        ];
                     }
-                    if let Some(arr) = win.get("size").and_then(|v| v.as_array()) {
-                        if arr.len() == 2 {
-                            size = [
-                                arr[0].as_float().unwrap_or(800.0) as f32,
-                                arr[1].as_float().unwrap_or(120.0) as f32,
-                            ];
+                }
+                if let Some(origin) = win.get("position_relative_to").and_then(|v| v.as_str()) {
+                    position_relative_to = match origin.to_ascii_lowercase().as_str() {
+                        "monitor" => PositionOrigin::Monitor,
+                        "desktop" => PositionOrigin::Desktop,
+                        other => {
+                            warn!("Invalid position_relative_to '{}'. Using desktop.", other);
+                            PositionOrigin::Desktop
                         }
+                    };
+                }
+                if let Some(index) = win.get("monitor").and_then(|v| v.as_integer()) {
+                    monitor = index.max(0) as usize;
+                }
+                // A numeric position always wins over an anchor
+                if win.get("position").is_none() {
+                    if let Some(name) = win.get("anchor").and_then(|v| v.as_str()) {
+                        anchor = parse_anchor(name);
+                    }
+                }
+                if let Some(m) = win.get("margin").and_then(|v| v.as_float()) {
+                    margin = [m as f32; 2];
+                }
+                if let Some(m) = win.get("margin_x").and_then(|v| v.as_float()) {
+                    margin[0] = m as f32;
+                }
+                if let Some(m) = win.get("margin_y").and_then(|v| v.as_float()) {
+                    margin[1] = m as f32;
+                }
+                if let Some(arr) = win.get("size").and_then(|v| v.as_array()) {
+                    if arr.len() == 2 {
+                        size = [
+                            arr[0].as_float().unwrap_or(800.0) as f32,
+                            arr[1].as_float().unwrap_or(120.0) as f32,
+                        ];
                     }
                 }
+                if let Some(width) = win.get("max_content_width").and_then(|v| v.as_float()) {
+                    max_content_width = width.max(0.0) as f32;
+                }
+                if let Some(height) = win.get("max_content_height").and_then(|v| v.as_float()) {
+                    max_content_height = height.max(0.0) as f32;
+                }
+                if let Some(mirror) = win.get("mirror_all_monitors").and_then(|v| v.as_bool()) {
+                    mirror_all_monitors = mirror;
+                }
+                if let Some(follow) = win.get("follow_cursor").and_then(|v| v.as_bool()) {
+                    follow_cursor = follow;
+                }
+                if let Some(on_top) = win.get("always_on_top").and_then(|v| v.as_bool()) {
+                    always_on_top = on_top;
+                }
+                if let Some(skip) = win.get("skip_taskbar").and_then(|v| v.as_bool()) {
+                    skip_taskbar = skip;
+                }
+                if let Some(decorated) = win.get("decorations").and_then(|v| v.as_bool()) {
+                    decorations = decorated;
+                }
+                if let Some(resize) = win.get("resizable").and_then(|v| v.as_bool()) {
+                    resizable = resize;
+                }
+                if let Some(blur) = win.get("blur_background").and_then(|v| v.as_bool()) {
+                    blur_background = blur;
+                }
+                if let Some(value) = win.get("grab_modifier").and_then(|v| v.as_str()) {
+                    grab_modifier = match value.to_ascii_lowercase().as_str() {
+                        "" | "none" => None,
+                        other => Modifier::parse(other).or_else(|| {
+                            warn!("Invalid grab_modifier '{}'. Using none.", value);
+                            None
+                        }),
+                    };
+                }
+                if let Some(title) = win.get("title").and_then(|v| v.as_str()) {
+                    window_title = title.to_string();
+                }
+                if let Some(id) = win.get("app_id").and_then(|v| v.as_str()) {
+                    app_id = id.to_string();
+                }
+            }
 
-                if let Some(s) = toml.get("styles") {
-                    for (cat, table) in s.as_table().unwrap_or(&toml::map::Map::new()) {
-                        if let Some(key_cat) = parse_category(cat) {
-                            let style = parse_style(table, &key_cat);
-                            styles.insert(key_cat, style);
+            if let Some(snd) = toml.get("sound") {
+                if let Some(enabled) = snd.get("enabled").and_then(|v| v.as_bool()) {
+                    sound.enabled = enabled;
+                }
+                if let Some(file) = snd.get("file").and_then(|v| v.as_str()) {
+                    sound.file = file.to_string();
+                }
+            }
+
+            if let Some(table) = toml.get("labels").and_then(|v| v.as_table()) {
+                for (key, text) in table {
+                    match text.as_str() {
+                        Some(text) => {
+                            labels.insert(key.clone(), text.to_string());
                         }
+                        None => warn!("Invalid label override for '{}'. Ignoring.", key),
                     }
                 }
+            }
+
+            if let Some(table) = toml.get("locale").and_then(|v| v.as_table()) {
+                locale = parse_locale(table);
+            }
+
+            if let Some(s) = toml.get("styles") {
+                for (cat, table) in s.as_table().unwrap_or(&toml::map::Map::new()) {
+                    if let Some(key_cat) = parse_category(cat) {
+                        let style = parse_style(table, &key_cat);
+                        styles.insert(key_cat, style);
+                    }
+                }
+            }
+
+            // A top-level `auto_width` applies to every category that doesn't set its own
+            if let Some(auto) = toml.get("auto_width").and_then(|v| v.as_bool()) {
+                let tables = toml.get("styles");
+                for (cat, style) in styles.iter_mut() {
+                    let own = tables
+                        .and_then(|s| s.get(category_name(cat)))
+                        .and_then(|t| t.get("auto_width"));
+                    if own.is_none() {
+                        style.auto_width = auto;
+                    }
+                }
+            }
+
+            // A top-level `[shadow]` applies to every category without its own
+            if let Some(shadow) = toml.get("shadow").map(parse_shadow) {
+                for style in styles.values_mut() {
+                    style.shadow.get_or_insert(shadow);
+                }
+            }
+
+            // Likewise a top-level `[text_outline]`
+            if let Some(outline) = toml.get("text_outline").map(parse_text_outline) {
+                for style in styles.values_mut() {
+                    style.text_outline.get_or_insert(outline);
+                }
+            }
+
+            panel = toml.get("panel").map(parse_panel);
+
+            if let Some(timeout) = toml.get("timeout_ms").and_then(|v| v.as_integer()) {
+                timeout_ms = timeout as u64;
+            }
+
+            if let Some(ms) = toml.get("double_click_ms").and_then(|v| v.as_integer()) {
+                double_click_ms = ms as u64;
+            }
+
+            if let Some(ms) = toml.get("debounce_ms").and_then(|v| v.as_integer()) {
+                debounce_ms = ms.max(0) as u64;
+            }
+            if let Some(ms) = toml.get("reload_interval_ms").and_then(|v| v.as_integer()) {
+                reload_interval_ms = ms.max(0) as u64;
+            }
+            if let Some(v) = toml.get("hot_reload").and_then(|v| v.as_bool()) {
+                hot_reload = v;
+            }
+            if let Some(v) = toml.get("watch_content").and_then(|v| v.as_bool()) {
+                watch_content = v;
+            }
+
+            if let Some(drag) = toml.get("show_mouse_drag").and_then(|v| v.as_bool()) {
+                show_mouse_drag = drag;
+            }
+
+            if let Some(value) = toml.get("show_unknown").and_then(|v| v.as_bool()) {
+                show_unknown = value;
+            }
+
+            if let Some(value) = toml.get("show_numpad_grid").and_then(|v| v.as_bool()) {
+                show_numpad_grid = value;
+            }
+
+            if let Some(value) = toml.get("log_unmapped").and_then(|v| v.as_bool()) {
+                log_unmapped = value;
+            }
+
+            if let Some(value) = toml.get("sticky").and_then(|v| v.as_bool()) {
+                sticky = value;
+            }
+
+            if let Some(value) = toml
+                .get("modifiers_only_in_chords")
+                .and_then(|v| v.as_bool())
+            {
+                modifiers_only_in_chords = value;
+            }
+
+            if let Some(value) = toml.get("trigger").and_then(|v| v.as_str()) {
+                trigger = match value.to_ascii_lowercase().replace('-', "_").as_str() {
+                    "always" => Trigger::Always,
+                    "modifier_held" => Trigger::ModifierHeld,
+                    other => {
+                        warn!("Invalid trigger '{}'. Using always.", other);
+                        Trigger::Always
+                    }
+                };
+            }
+
+            if let Some(value) = toml.get("sort_simultaneous").and_then(|v| v.as_str()) {
+                sort_simultaneous = match value.to_ascii_lowercase().replace('-', "_").as_str() {
+                    "time" => SortSimultaneous::Time,
+                    "modifier_first" => SortSimultaneous::ModifierFirst,
+                    other => {
+                        warn!("Invalid sort_simultaneous '{}'. Using time.", other);
+                        SortSimultaneous::Time
+                    }
+                };
+            }
+
+            if let Some(value) = toml.get("empty_label").and_then(|v| v.as_str()) {
+                empty_label = match value.to_ascii_lowercase().as_str() {
+                    "skip" => EmptyLabel::Skip,
+                    "placeholder" => EmptyLabel::Placeholder,
+                    other => {
+                        warn!("Invalid empty_label '{}'. Using placeholder.", other);
+                        EmptyLabel::Placeholder
+                    }
+                };
+            }
+
+            if let Some(value) = toml.get("display_mode").and_then(|v| v.as_str()) {
+                display_mode = match value.to_ascii_lowercase().as_str() {
+                    "keys" => DisplayMode::Keys,
+                    "caption" => DisplayMode::Caption,
+                    other => {
+                        warn!("Invalid display_mode '{}'. Using keys.", other);
+                        DisplayMode::Keys
+                    }
+                };
+            }
+
+            if let Some(arr) = toml.get("only_when_focused").and_then(|v| v.as_array()) {
+                only_when_focused = arr
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect();
+            }
+
+            if let Some(v) = toml.get("show_capture_indicator").and_then(|v| v.as_bool()) {
+                show_capture_indicator = v;
+            }
+
+            if let Some(value) = toml.get("group_text").and_then(|v| v.as_bool()) {
+                group_text = value;
+            }
+
+            if let Some(value) = toml.get("group_text_ms").and_then(|v| v.as_integer()) {
+                group_text_ms = value.max(0) as u64;
+            }
+
+            if let Some(value) = toml.get("clear_hotkey").and_then(|v| v.as_str()) {
+                clear_hotkey = Hotkey::parse(value);
+                if clear_hotkey.is_none() && !value.is_empty() {
+                    warn!("Invalid clear_hotkey '{}'. Ignoring.", value);
+                }
+            }
+
+            if let Some(arr) = toml.get("theme_cycle").and_then(|v| v.as_array()) {
+                theme_cycle = arr
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .filter(|name| {
+                        let known = Theme::named(name).is_some();
+                        if !known {
+                            warn!("Unknown theme '{}' in theme_cycle. Skipping it.", name);
+                        }
+                        known
+                    })
+                    .map(String::from)
+                    .collect();
+            }
+
+            if let Some(value) = toml.get("theme_cycle_hotkey").and_then(|v| v.as_str()) {
+                theme_cycle_hotkey = Hotkey::parse(value);
+                if theme_cycle_hotkey.is_none() && !value.is_empty() {
+                    warn!("Invalid theme_cycle_hotkey '{}'. Ignoring.", value);
+                }
+            }
+
+            if let Some(value) = toml.get("screenshot_hotkey").and_then(|v| v.as_str()) {
+                screenshot_hotkey = Hotkey::parse(value);
+                if screenshot_hotkey.is_none() && !value.is_empty() {
+                    warn!("Invalid screenshot_hotkey '{}'. Ignoring.", value);
+                }
+            }
+
+            if let Some(value) = toml.get("settings_hotkey").and_then(|v| v.as_str()) {
+                settings_hotkey = Hotkey::parse(value);
+                if settings_hotkey.is_none() && !value.is_empty() {
+                    warn!("Invalid settings_hotkey '{}'. Ignoring.", value);
+                }
+            }
+
+            if let Some(value) = toml.get("fixed_slots").and_then(|v| v.as_integer()) {
+                fixed_slots = value as usize;
+            }
+
+            if let Some(value) = toml.get("max_fps").and_then(|v| v.as_integer()) {
+                match u64::try_from(value) {
+                    Ok(fps) => max_fps = fps,
+                    Err(_) => warn!("Invalid max_fps '{}'. Using {}.", value, max_fps),
+                }
+            }
+
+            if let Some(value) = toml
+                .get("max_animation_step_ms")
+                .and_then(|v| v.as_integer())
+            {
+                max_animation_step_ms = value.max(0) as u64;
+            }
+
+            if let Some(value) = toml.get("render_mode").and_then(|v| v.as_str()) {
+                render_mode = match value {
+                    "continuous" => RenderMode::Continuous,
+                    "reactive" => RenderMode::Reactive,
+                    other => {
+                        warn!("Invalid render_mode '{}'. Using continuous.", other);
+                        RenderMode::Continuous
+                    }
+                };
+            }
 
-                if let Some(timeout) = toml.get("timeout_ms").and_then(|v| v.as_integer()) {
-                    timeout_ms = timeout as u64;
+            if let Some(name) = toml.get("layout").and_then(|v| v.as_str()) {
+                layout = KeyboardLayout::from_name(name);
+                if layout.is_none() && !name.is_empty() {
+                    warn!("Unknown layout '{}'. Using auto-detection.", name);
                 }
             }
+
+            if let Some(value) = toml.get("show_layout").and_then(|v| v.as_bool()) {
+                show_layout = value;
+            }
+
+            if let Some(value) = toml.get("compose").and_then(|v| v.as_bool()) {
+                compose = value;
+            }
+
+            if let Some(value) = toml.get("glyphs").and_then(|v| v.as_bool()) {
+                glyphs = value;
+            }
+
+            if let Some(value) = toml.get("compact").and_then(|v| v.as_bool()) {
+                compact = value;
+            }
+
+            if let Some(value) = toml.get("pixel_snap").and_then(|v| v.as_bool()) {
+                pixel_snap = value;
+            }
+
+            if let Some(value) = toml.get("row_align").and_then(|v| v.as_str()) {
+                row_align = match value.to_ascii_lowercase().as_str() {
+                    "top" => RowAlign::Top,
+                    "center" => RowAlign::Center,
+                    "bottom" => RowAlign::Bottom,
+                    other => {
+                        warn!("Invalid row_align '{}'. Using bottom.", other);
+                        RowAlign::Bottom
+                    }
+                };
+            }
+
+            if let Some(value) = toml.get("overflow").and_then(|v| v.as_str()) {
+                overflow = match value.to_ascii_lowercase().as_str() {
+                    "trim" => Overflow::Trim,
+                    "scroll" => Overflow::Scroll,
+                    other => {
+                        warn!("Invalid overflow '{}'. Using trim.", other);
+                        Overflow::Trim
+                    }
+                };
+            }
+
+            if let Some(v) = toml.get("highlight_latest").and_then(|v| v.as_bool()) {
+                highlight_latest = v;
+            }
+            if let Some(color) = toml.get("highlight_color").and_then(|v| v.as_str()) {
+                highlight_color = hex(color);
+            }
+            if let Some(width) = toml.get("highlight_border").and_then(|v| v.as_float()) {
+                highlight_border = width.max(0.0) as f32;
+            }
+            if let Some(v) = toml.get("pulse_held").and_then(|v| v.as_bool()) {
+                pulse_held = v;
+            }
+
+            if let Some(v) = toml.get("velocity_visualization").and_then(|v| v.as_bool()) {
+                velocity_visualization = v;
+            }
+
+            if let Some(value) = toml.get("entry_animation").and_then(|v| v.as_str()) {
+                entry_animation = Animation::parse("entry_animation", value, Animation::Scale);
+            }
+            if let Some(value) = toml.get("exit_animation").and_then(|v| v.as_str()) {
+                exit_animation = Animation::parse("exit_animation", value, Animation::None);
+            }
+
+            if let Some(value) = toml.get("modifier_style").and_then(|v| v.as_str()) {
+                modifier_style = match value.to_ascii_lowercase().as_str() {
+                    "glyph" => ModifierStyle::Glyph,
+                    "word" => ModifierStyle::Word,
+                    "both" => ModifierStyle::Both,
+                    other => {
+                        warn!("Invalid modifier_style '{}'. Using both.", other);
+                        ModifierStyle::Both
+                    }
+                };
+            }
         }
 
-        let mut config = Config {
+        Config {
             styles,
             timeout_ms,
+            double_click_ms,
+            debounce_ms,
+            show_mouse_drag,
+            show_unknown,
+            show_numpad_grid,
+            log_unmapped,
+            sticky,
+            modifiers_only_in_chords,
+            trigger,
+            sort_simultaneous,
+            empty_label,
+            display_mode,
+            only_when_focused,
+            show_capture_indicator,
+            group_text,
+            group_text_ms,
+            clear_hotkey,
+            theme_cycle,
+            theme_cycle_hotkey,
+            screenshot_hotkey,
+            settings_hotkey,
+            fixed_slots,
+            max_fps,
+            render_mode,
+            max_animation_step_ms,
+            reload_interval_ms,
+            hot_reload,
+            watch_content,
+            modifier_style,
+            glyphs,
+            layout,
+            show_layout,
+            compose,
+            compact,
+            pixel_snap,
+            row_align,
+            overflow,
+            entry_animation,
+            exit_animation,
+            highlight_latest,
+            highlight_color,
+            highlight_border,
+            panel,
+            pulse_held,
+            velocity_visualization,
             position,
+            position_relative_to,
+            monitor,
+            anchor,
+            margin,
             size,
-            path: path.to_string(),
-            last_modified,
+            max_content_width,
+            max_content_height,
+            mirror_all_monitors,
+            follow_cursor,
+            always_on_top,
+            skip_taskbar,
+            decorations,
+            resizable,
+            grab_modifier,
+            blur_background,
+            window_title,
+            app_id,
+            sound,
+            labels,
+            locale,
+            path: path.to_path_buf(),
+            last_modified: None,
+            content_hash: None,
+            last_reload: None,
             reload_rx: None,
-        };
-
-        config.setup_watcher();
-        config
-    }
-
-    /// Loads a default config with no file watching.
-    pub fn default() -> Self {
-        let mut c = Config::load("does-not-exist.toml");
-        c.reload_rx = None;
-        c
+        }
     }
 
     /// Sets up a filesystem watcher on the config file.
     /// Emits a signal over a channel when the file is modified.
+    #[cfg(feature = "hot-reload")]
     fn setup_watcher(&mut self) {
         let (tx, rx) = channel();
         let path = self.path.clone();
@@ -201,7 +1515,7 @@ impl Config {
         }
 
         thread::spawn(move || {
-            let mut watcher = RecommendedWatcher::new(
+            let watcher = RecommendedWatcher::new(
                 move |res: notify::Result<notify::Event>| {
                     if let Ok(event) = res {
                         if matches!(event.kind, EventKind::Modify(_)) {
@@ -210,13 +1524,20 @@ impl Config {
                     }
                 },
                 NotifyConfig::default(),
-            )
-            .expect("Failed to create watcher");
+            );
 
-            if let Err(e) = watcher.watch(Path::new(&path), RecursiveMode::NonRecursive) {
-                eprintln!("⚠️ Failed to watch config file: {e}");
-                return;
-            }
+            // Without a watcher, edits are still picked up by the mtime check in `maybe_reload`
+            let watching = watcher.and_then(|mut watcher| {
+                watcher.watch(Path::new(&path), RecursiveMode::NonRecursive)?;
+                Ok(watcher)
+            });
+            let _watcher = match watching {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    warn!("{}", FerrisKeysError::from(e));
+                    return;
+                }
+            };
 
             loop {
                 thread::sleep(Duration::from_secs(3600));
@@ -226,45 +1547,185 @@ impl Config {
         self.reload_rx = Some(rx);
     }
 
+    /// Without the `hot-reload` feature there is no watcher; `maybe_reload` still notices
+    /// edits by polling the file's modification time.
+    #[cfg(not(feature = "hot-reload"))]
+    fn setup_watcher(&mut self) {
+        self.reload_rx = None;
+    }
+
     /// Checks whether the config file has changed.
     /// If so, reloads it and updates the current instance.
     ///
+    /// Reloads are at most one per `reload_interval_ms`. Changes made in between are not
+    /// lost: the watcher signal stays queued and the newer mtime is picked up next time.
+    ///
+    /// With `hot_reload = false` the file is never checked, until FerrisKeys restarts.
+    ///
     /// # Returns
     /// `true` if the config was reloaded, `false` otherwise.
     pub fn maybe_reload(&mut self) -> bool {
+        if !self.hot_reload {
+            return false;
+        }
+
+        let interval = Duration::from_millis(self.reload_interval_ms);
+        if self
+            .last_reload
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return false;
+        }
+
         let mut triggered = false;
 
         if let Some(rx) = &self.reload_rx {
-            if rx.try_recv().is_ok() {
+            // Drain the whole burst; one reload covers every queued change
+            while rx.try_recv().is_ok() {
                 triggered = true;
             }
         }
 
-        if !triggered {
-            if let Ok(modified) = fs::metadata(&self.path).and_then(|m| m.modified()) {
-                if Some(modified) > self.last_modified {
-                    triggered = true;
-                }
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        // Any difference counts: restoring a backup or a clock change can move mtime backwards
+        if modified.is_some() && modified != self.last_modified {
+            triggered = true;
+        }
+
+        let current_hash = if triggered || self.watch_content {
+            fs::read_to_string(&self.path)
+                .ok()
+                .map(|content| hash_content(&content))
+        } else {
+            None
+        };
+
+        if current_hash.is_some() {
+            if current_hash != self.content_hash {
+                triggered = true;
+            } else if triggered {
+                // Touched but unchanged (e.g. saved without edits): nothing to reload
+                self.last_modified = modified;
+                return false;
             }
         }
 
         if triggered {
-            *self = Config::load(&self.path);
+            match Config::load(&self.path) {
+                Ok(config) => *self = config,
+                Err(e) => {
+                    // Keep the current settings until the file is fixed
+                    warn!("{e}. Keeping the previous settings.");
+                    self.last_modified = modified;
+                    self.content_hash = current_hash;
+                    self.last_reload = Some(Instant::now());
+                    return false;
+                }
+            }
+            self.last_reload = Some(Instant::now());
             return true;
         }
 
         false
     }
 
+    /// Writes the settings editable in the settings window back to the config file:
+    /// `timeout_ms`, the window position and size, and each category's sizes and colors.
+    ///
+    /// Every other key in the file is kept, along with comments and formatting. A file
+    /// that isn't valid TOML is left untouched and reported as an error, as is saving an
+    /// `in_memory` config.
+    pub fn save(&self) -> Result<(), FerrisKeysError> {
+        if self.file().is_none() {
+            return Err(FerrisKeysError::NoConfigFile);
+        }
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(FerrisKeysError::io(&self.path, e)),
+        };
+        let parse_error = |source| FerrisKeysError::ConfigParse {
+            path: self.path.clone(),
+            source,
+        };
+        toml::from_str::<toml::Table>(&content).map_err(parse_error)?;
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .map_err(|e| FerrisKeysError::io(&self.path, io::Error::other(e)))?;
+
+        self.write_settings(&mut doc);
+
+        fs::write(&self.path, doc.to_string()).map_err(|e| FerrisKeysError::io(&self.path, e))
+    }
+
+    /// Writes the settings covered by `save` into `doc`, replacing existing values but
+    /// keeping their comments.
+    fn write_settings(&self, doc: &mut DocumentMut) {
+        set_value(doc, "timeout_ms", (self.timeout_ms as i64).into());
+
+        let window = child_table(doc, "window");
+        set_value(window, "position", float_array(&self.position));
+        set_value(window, "size", float_array(&self.size));
+
+        let styles = child_table(doc, "styles");
+        styles.set_implicit(true);
+        let mut categories: Vec<_> = self.styles.iter().collect();
+        categories.sort_by_key(|(cat, _)| category_name(cat));
+        for (cat, style) in categories {
+            let table = child_table(styles, category_name(cat));
+            set_value(table, "width", float(style.width));
+            set_value(table, "height", float(style.height));
+            set_value(table, "icon_size", float(style.icon_size));
+            set_value(table, "text_size", float(style.text_size));
+            set_value(table, "bg_color", to_hex(style.bg_color).into());
+            set_value(table, "fg_color", to_hex(style.fg_color).into());
+        }
+    }
+
+    /// Returns whether clicks should pass through the overlay: only while it has no title
+    /// bar or borders to click, so `decorations` and `resizable` windows can be moved and sized.
+    pub fn mouse_passthrough(&self) -> bool {
+        !(self.decorations || self.resizable)
+    }
+
+    /// Returns the window level matching the `always_on_top` setting.
+    pub fn window_level(&self) -> WindowLevel {
+        if self.always_on_top {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        }
+    }
+
+    /// Returns the X11 window type matching `skip_taskbar`.
+    ///
+    /// winit only hides windows from the taskbar on Windows; on X11, window managers keep
+    /// utility windows out of the taskbar and Alt-Tab instead.
+    pub fn x11_window_type(&self) -> X11WindowType {
+        if self.skip_taskbar {
+            X11WindowType::Utility
+        } else {
+            X11WindowType::Normal
+        }
+    }
+
     /// Provides a fallback visual style if a key category is missing in config.
     pub fn fallback_style() -> Style {
         Style {
             width: 90.0,
+            min_width: None,
+            max_width: None,
+            auto_width: false,
             height: 90.0,
             icon_size: 0.0,
             text_size: 24.0,
             bg_color: hex("#3c3c3c"),
             fg_color: hex("ffffff"),
+            bg_color2: None,
+            gradient_direction: GradientDirection::Vertical,
+            icon_position: IconPosition::Above,
+            shadow: None,
+            text_outline: None,
         }
     }
 
@@ -278,11 +1739,19 @@ impl Config {
                 cat,
                 Style {
                     width: w,
+                    min_width: None,
+                    max_width: None,
+                    auto_width: false,
                     height: h,
                     icon_size: icon,
                     text_size: text,
                     bg_color: hex(bg),
                     fg_color: hex(fg),
+                    bg_color2: None,
+                    gradient_direction: GradientDirection::Vertical,
+                    icon_position: IconPosition::Above,
+                    shadow: None,
+                    text_outline: None,
                 },
             );
         };
@@ -304,20 +1773,77 @@ impl Config {
     }
 }
 
-/// Converts a `"#RRGGBB"` color string to a `Color32` value.
+/// Converts a `"#RRGGBB"` or `"#RRGGBBAA"` color string to a `Color32` value.
 /// Falls back to white if the string is malformed.
 fn hex(c: &str) -> Color32 {
     let cleaned = c.trim_start_matches('#');
-    if cleaned.len() != 6 {
-        eprintln!("Invalid color string: '{}'. Using fallback.", c);
+    if !is_hex_color(c) {
+        warn!("Invalid color string: '{}'. Using fallback.", c);
         return Color32::WHITE;
     }
 
-    let r = u8::from_str_radix(&cleaned[0..2], 16).unwrap_or(255);
-    let g = u8::from_str_radix(&cleaned[2..4], 16).unwrap_or(255);
-    let b = u8::from_str_radix(&cleaned[4..6], 16).unwrap_or(255);
+    let channel = |i: usize| u8::from_str_radix(&cleaned[i..i + 2], 16).unwrap_or(255);
+    let alpha = if cleaned.len() == 8 { channel(6) } else { 255 };
 
-    Color32::from_rgb(r, g, b)
+    Color32::from_rgba_unmultiplied(channel(0), channel(2), channel(4), alpha)
+}
+
+/// Returns whether `c` has the length of a `#rrggbb` or `#rrggbbaa` color.
+fn is_hex_color(c: &str) -> bool {
+    matches!(c.trim_start_matches('#').len(), 6 | 8)
+}
+
+/// Formats a color as a `#rrggbb` string (`#rrggbbaa` if translucent), the inverse of `hex`.
+pub(crate) fn to_hex(c: Color32) -> String {
+    let [r, g, b, a] = c.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Converts a float for saving, rounded so `f32` values don't gain noise digits.
+fn float(value: f32) -> toml_edit::Value {
+    rounded(value).into()
+}
+
+/// Rounds a float to two decimals, e.g. `0.3f32` to `0.3` rather than `0.30000001192092896`.
+pub(crate) fn rounded(value: f32) -> f64 {
+    (value as f64 * 100.0).round() / 100.0
+}
+
+/// Converts a pair of floats for saving.
+fn float_array(values: &[f32; 2]) -> toml_edit::Value {
+    values
+        .iter()
+        .map(|v| float(*v))
+        .collect::<toml_edit::Array>()
+        .into()
+}
+
+/// Sets `key` in `table` to `value`, keeping the comments around an existing value.
+fn set_value(table: &mut toml_edit::Table, key: &str, mut value: toml_edit::Value) {
+    match table.get_mut(key).and_then(toml_edit::Item::as_value_mut) {
+        Some(old) => {
+            *value.decor_mut() = old.decor().clone();
+            *old = value;
+        }
+        None => {
+            table.insert(key, toml_edit::Item::Value(value));
+        }
+    }
+}
+
+/// Returns the sub-table `key` of `table`, replacing any other value with an empty table.
+fn child_table<'a>(table: &'a mut toml_edit::Table, key: &str) -> &'a mut toml_edit::Table {
+    let item = table.entry(key).or_insert(toml_edit::table());
+    if let Some(inline) = item.as_inline_table() {
+        *item = toml_edit::Item::Table(inline.clone().into_table());
+    } else if !item.is_table() {
+        *item = toml_edit::table();
+    }
+    item.as_table_mut().expect("just made a table")
 }
 
 /// Parses a `Style` table from TOML with fallbacks for each field.
@@ -329,7 +1855,7 @@ fn parse_style(table: &Value, category: &KeyCategory) -> Style {
 
     let get = |k: &str| {
         table.get(k).and_then(|v| v.as_float()).unwrap_or_else(|| {
-            eprintln!(
+            warn!(
                 "Missing or invalid `{}` for {:?}. Using fallback.",
                 k, category
             );
@@ -347,11 +1873,10 @@ fn parse_style(table: &Value, category: &KeyCategory) -> Style {
         let val = table.get(k).and_then(|v| v.as_str());
         match val {
             Some(color) => {
-                let cleaned = color.trim_start_matches('#');
-                if cleaned.len() == 6 {
+                if is_hex_color(color) {
                     hex(color)
                 } else {
-                    eprintln!("Invalid color '{}'. Falling back.", color);
+                    warn!("Invalid color '{}'. Falling back.", color);
                     match k {
                         "bg_color" => fallback.bg_color,
                         "text_color" => fallback.fg_color,
@@ -360,7 +1885,7 @@ fn parse_style(table: &Value, category: &KeyCategory) -> Style {
                 }
             }
             None => {
-                eprintln!(
+                warn!(
                     "Missing color key `{}` for {:?}. Using fallback.",
                     k, category
                 );
@@ -373,16 +1898,228 @@ fn parse_style(table: &Value, category: &KeyCategory) -> Style {
         }
     };
 
+    // `aspect_ratio` derives the height from the width, so only `width` needs tuning
+    let width = get("width");
+    let height = match table.get("aspect_ratio").and_then(parse_aspect_ratio) {
+        Some(ratio) => width / ratio,
+        None => get("height"),
+    };
+
+    let optional = |k: &str| {
+        table
+            .get(k)
+            .and_then(|v| v.as_float())
+            .map(|v| v.max(0.0) as f32)
+    };
+
     Style {
-        width: get("width"),
-        height: get("height"),
+        width,
+        min_width: optional("min_width"),
+        max_width: optional("max_width"),
+        auto_width: table
+            .get("auto_width")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        height,
         icon_size: get("icon_size"),
         text_size: get("text_size"),
         bg_color: get_color("bg_color"),
         fg_color: get_color("fg_color"),
+        bg_color2: table
+            .get("bg_color2")
+            .and_then(|v| v.as_str())
+            .and_then(|color| {
+                if is_hex_color(color) {
+                    Some(hex(color))
+                } else {
+                    warn!("Invalid color '{}'. Using a flat background.", color);
+                    None
+                }
+            }),
+        gradient_direction: match table.get("gradient_direction").and_then(|v| v.as_str()) {
+            Some("horizontal") => GradientDirection::Horizontal,
+            Some("vertical") | None => GradientDirection::Vertical,
+            Some(other) => {
+                warn!("Invalid gradient_direction '{}'. Using vertical.", other);
+                GradientDirection::Vertical
+            }
+        },
+        icon_position: match table.get("icon_position").and_then(|v| v.as_str()) {
+            Some("below") => IconPosition::Below,
+            Some("left") => IconPosition::Left,
+            Some("right") => IconPosition::Right,
+            Some("above") | None => IconPosition::Above,
+            Some(other) => {
+                warn!("Invalid icon_position '{}'. Using above.", other);
+                IconPosition::Above
+            }
+        },
+        shadow: table.get("shadow").map(parse_shadow),
+        text_outline: table.get("text_outline").map(parse_text_outline),
     }
 }
 
+/// Parses an `aspect_ratio` (width / height) given as a number like `1.5`, a `"4:3"` string,
+/// or `"square"`. Returns `None`, with a warning, for anything that isn't a positive ratio.
+fn parse_aspect_ratio(value: &Value) -> Option<f32> {
+    let ratio = match value {
+        Value::Float(f) => Some(*f),
+        Value::Integer(i) => Some(*i as f64),
+        Value::String(s) if s == "square" => Some(1.0),
+        Value::String(s) => s.split_once(':').and_then(|(w, h)| {
+            let (w, h) = (w.trim().parse::<f64>().ok()?, h.trim().parse::<f64>().ok()?);
+            Some(w / h)
+        }),
+        _ => None,
+    };
+
+    match ratio {
+        Some(r) if r.is_finite() && r > 0.0 => Some(r as f32),
+        _ => {
+            warn!("Invalid aspect_ratio '{}'. Using height.", value);
+            None
+        }
+    }
+}
+
+/// Parses a `shadow` table, e.g. `{ offset = [4.0, 4.0], blur = 6.0, color = "#000000", opacity = 0.5 }`.
+fn parse_shadow(table: &Value) -> Shadow {
+    let offset = match table.get("offset").and_then(|v| v.as_array()) {
+        Some(arr) if arr.len() == 2 => [
+            arr[0].as_float().unwrap_or(4.0) as f32,
+            arr[1].as_float().unwrap_or(4.0) as f32,
+        ],
+        _ => [4.0, 4.0],
+    };
+    let blur = table.get("blur").and_then(|v| v.as_float()).unwrap_or(6.0) as f32;
+    let color = table
+        .get("color")
+        .and_then(|v| v.as_str())
+        .map(hex)
+        .unwrap_or(Color32::BLACK);
+    let opacity = table
+        .get("opacity")
+        .and_then(|v| v.as_float())
+        .unwrap_or(0.5)
+        .clamp(0.0, 1.0) as f32;
+
+    Shadow {
+        offset,
+        blur: blur.max(0.0),
+        color: color.gamma_multiply(opacity),
+    }
+}
+
+/// Parses a `text_outline` table, e.g. `{ width = 1.5, color = "#000000", opacity = 1.0 }`.
+fn parse_text_outline(table: &Value) -> TextOutline {
+    let width = table.get("width").and_then(|v| v.as_float()).unwrap_or(1.5) as f32;
+    let color = table
+        .get("color")
+        .and_then(|v| v.as_str())
+        .map(hex)
+        .unwrap_or(Color32::BLACK);
+    let opacity = table
+        .get("opacity")
+        .and_then(|v| v.as_float())
+        .unwrap_or(1.0)
+        .clamp(0.0, 1.0) as f32;
+
+    TextOutline {
+        width: width.max(0.0),
+        color: color.gamma_multiply(opacity),
+    }
+}
+
+/// Parses a `panel` table, e.g. `{ color = "#000000", opacity = 0.4, padding = 8.0, rounding = 12.0 }`.
+fn parse_panel(table: &Value) -> Panel {
+    let color = table
+        .get("color")
+        .and_then(|v| v.as_str())
+        .map(hex)
+        .unwrap_or(Color32::BLACK);
+    let opacity = table
+        .get("opacity")
+        .and_then(|v| v.as_float())
+        .unwrap_or(0.4)
+        .clamp(0.0, 1.0) as f32;
+    let padding = table
+        .get("padding")
+        .and_then(|v| v.as_float())
+        .unwrap_or(8.0) as f32;
+    let content_padding = table
+        .get("content_padding")
+        .and_then(|v| v.as_float())
+        .map_or(padding, |v| v as f32);
+    let rounding = table
+        .get("rounding")
+        .and_then(|v| v.as_float())
+        .unwrap_or(12.0) as f32;
+
+    Panel {
+        color: color.gamma_multiply(opacity),
+        padding: padding.max(0.0),
+        content_padding: content_padding.max(0.0),
+        rounding: rounding.max(0.0),
+    }
+}
+
+/// Parses the `[locale]` table: the bundled translations for its `language`, if any, then
+/// its other entries as per-word translations on top.
+fn parse_locale(table: &toml::Table) -> HashMap<String, String> {
+    let mut words: Vec<(String, String)> = Vec::new();
+    if let Some(language) = table.get("language").and_then(|v| v.as_str()) {
+        match locale::preset(language) {
+            Some(preset) => words.extend(
+                preset
+                    .iter()
+                    .map(|(word, text)| (word.to_string(), text.to_string())),
+            ),
+            None => warn!("Unknown locale language '{}'. Ignoring.", language),
+        }
+    }
+    for (word, text) in table.iter().filter(|(key, _)| *key != "language") {
+        match text.as_str() {
+            Some(text) => words.push((word.to_lowercase(), text.to_string())),
+            None => warn!("Invalid locale entry for '{}'. Ignoring.", word),
+        }
+    }
+
+    // Labels show words lowercase or capitalized, so match (and keep) either form
+    let capitalize = |s: &str| {
+        let mut chars = s.chars();
+        chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    };
+    let mut locale = HashMap::new();
+    for (word, text) in words {
+        locale.insert(capitalize(&word), capitalize(&text));
+        locale.insert(word, text);
+    }
+    locale
+}
+
+/// Parses an anchor name such as `"bottom-center"` into an `Anchor`.
+fn parse_anchor(name: &str) -> Option<Anchor> {
+    use Anchor::*;
+    Some(match name.to_ascii_lowercase().replace('_', "-").as_str() {
+        "top-left" => TopLeft,
+        "top-center" => TopCenter,
+        "top-right" => TopRight,
+        "center-left" => CenterLeft,
+        "center" => Center,
+        "center-right" => CenterRight,
+        "bottom-left" => BottomLeft,
+        "bottom-center" => BottomCenter,
+        "bottom-right" => BottomRight,
+        other => {
+            warn!("Invalid anchor '{}'. Using position.", other);
+            return None;
+        }
+    })
+}
+
 /// Parses a string into a known `KeyCategory`.
 fn parse_category(name: &str) -> Option<KeyCategory> {
     use KeyCategory::*;
@@ -404,6 +2141,26 @@ fn parse_category(name: &str) -> Option<KeyCategory> {
     })
 }
 
+/// Returns the `[styles.*]` table name for a category, the inverse of `parse_category`.
+pub fn category_name(category: &KeyCategory) -> &'static str {
+    use KeyCategory::*;
+    match category {
+        Escape => "escape",
+        Normal => "normal",
+        Numeric => "numeric",
+        Modifier => "modifier",
+        Editor => "editor",
+        Navigation => "navigation",
+        Scrollable => "scrollable",
+        Space => "space",
+        Symbol => "symbol",
+        Function => "function",
+        AltFunction => "altfunction",
+        Unknown => "unknown",
+        Mouse => "mouse",
+    }
+}
+
 /// Registers and applies a bundled Nerd Font for both monospace and proportional rendering.
 pub fn setup_custom_fonts(ctx: &egui::Context) {
     let mut fonts = FontDefinitions::default();
@@ -426,3 +2183,305 @@ pub fn setup_custom_fonts(ctx: &egui::Context) {
         .insert(0, "NerdFont".to_owned());
     ctx.set_fonts(fonts);
 }
+
+/// Cheap, non-cryptographic hash of the config file's contents for change detection.
+fn hash_content(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{with_temp_config, TempPath};
+    use std::fs::File;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn hotkey_needs_exactly_its_modifiers() {
+        let hotkey = Hotkey::parse("ctrl+shift+k").unwrap();
+
+        assert!(!hotkey.matches(&["control"], "K"));
+        assert!(hotkey.matches(&["shift", "control"], "K"));
+        assert!(!hotkey.matches(&["control", "shift", "alt"], "K"));
+    }
+
+    #[test]
+    fn hotkey_parsing_rejects_two_keys() {
+        assert!(Hotkey::parse("ctrl+a+b").is_none());
+        assert!(Hotkey::parse("ctrl+shift").is_none());
+        assert_eq!(
+            Hotkey::parse("Shift + F5").unwrap(),
+            Hotkey {
+                modifiers: vec!["shift"],
+                key: "f5".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn reloads_when_mtime_moves_backwards() {
+        with_temp_config("timeout_ms = 1000\n", |path| {
+            let mut config = Config::load(path).unwrap();
+            config.reload_rx = None; // exercise the mtime path only
+            assert_eq!(config.timeout_ms, 1000);
+
+            fs::write(path, "timeout_ms = 2000\n").unwrap();
+            let past = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(past)
+                .unwrap();
+
+            assert!(config.maybe_reload());
+            assert_eq!(config.timeout_ms, 2000);
+            assert_eq!(config.last_modified, Some(past));
+        });
+    }
+
+    #[test]
+    fn touching_without_edits_does_not_reload() {
+        with_temp_config("timeout_ms = 1000\n", |path| {
+            let mut config = Config::load(path).unwrap();
+            config.reload_rx = None;
+
+            let past = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(past)
+                .unwrap();
+
+            assert!(!config.maybe_reload());
+            assert_eq!(config.last_modified, Some(past));
+        });
+    }
+
+    #[test]
+    fn watch_content_reloads_when_mtime_is_unchanged() {
+        with_temp_config("timeout_ms = 1000\nwatch_content = true\n", |path| {
+            let mut config = Config::load(path).unwrap();
+            config.reload_rx = None;
+            let mtime = config.last_modified.unwrap();
+
+            fs::write(path, "timeout_ms = 2000\nwatch_content = true\n").unwrap();
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+
+            assert!(config.maybe_reload());
+            assert_eq!(config.timeout_ms, 2000);
+        });
+    }
+
+    #[test]
+    fn invalid_toml_is_an_error_and_keeps_settings_on_reload() {
+        with_temp_config("timeout_ms = \n", |path| {
+            assert!(matches!(
+                Config::load(path),
+                Err(FerrisKeysError::ConfigParse { .. })
+            ));
+
+            fs::write(path, "timeout_ms = 1000\n").unwrap();
+            let mut config = Config::load(path).unwrap();
+            config.reload_rx = None;
+
+            fs::write(path, "timeout_ms = [\n").unwrap();
+            let past = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(past)
+                .unwrap();
+
+            assert!(!config.maybe_reload());
+            assert_eq!(config.timeout_ms, 1000);
+            assert!(config.save().is_err());
+        });
+    }
+
+    #[test]
+    fn save_keeps_comments_and_translucent_colors() {
+        with_temp_config("# My overlay\ntimeout_ms = 1000 # a bit longer\n", |path| {
+            let mut config = Config::load(path).unwrap();
+            config.timeout_ms = 2000;
+            let normal = config.styles.get_mut(&KeyCategory::Normal).unwrap();
+            normal.bg_color = Color32::from_rgba_unmultiplied(0, 0, 0, 128);
+            config.save().unwrap();
+
+            let saved = fs::read_to_string(path).unwrap();
+            assert!(saved.starts_with("# My overlay\ntimeout_ms = 2000 # a bit longer\n"));
+            assert!(saved.contains("bg_color = \"#00000080\""));
+            let loaded = Config::load(path).unwrap();
+            assert_eq!(loaded.styles[&KeyCategory::Normal].bg_color.a(), 128);
+        });
+    }
+
+    #[test]
+    fn dumped_config_loads_back_unchanged() {
+        let mut config = Config {
+            timeout_ms: 1234,
+            trigger: Trigger::ModifierHeld,
+            clear_hotkey: Hotkey::parse("ctrl+shift+k"),
+            anchor: Some(Anchor::BottomCenter),
+            grab_modifier: Some(Modifier::Meta),
+            panel: Some(Panel {
+                color: Color32::from_black_alpha(100),
+                padding: 8.0,
+                content_padding: 4.0,
+                rounding: 12.0,
+            }),
+            ..Config::default()
+        };
+        config.styles.get_mut(&KeyCategory::Modifier).unwrap().width = 150.0;
+        config.labels.insert("Space".into(), "⎵".into());
+        let table: Value = toml::from_str(&config.to_toml().unwrap()).unwrap();
+        let loaded = Config::from_toml(Some(&table), Path::new(""));
+        assert_eq!(loaded.timeout_ms, 1234);
+        assert_eq!(loaded.styles[&KeyCategory::Modifier].width, 150.0);
+        assert_eq!(loaded.trigger, Trigger::ModifierHeld);
+        assert_eq!(loaded.clear_hotkey, config.clear_hotkey);
+        assert_eq!(loaded.anchor, Some(Anchor::BottomCenter));
+        assert_eq!(loaded.grab_modifier, Some(Modifier::Meta));
+        assert_eq!(loaded.panel, config.panel);
+        assert_eq!(loaded.labels["Space"], "⎵");
+        assert_eq!(loaded.to_toml().unwrap(), config.to_toml().unwrap());
+    }
+
+    #[test]
+    fn locale_overrides_bundled_words_in_either_case() {
+        let table: toml::Table = toml::from_str("language = \"de\"\nhome = \"anfang\"").unwrap();
+        let locale = parse_locale(&table);
+        assert_eq!(locale["shift"], "umschalt");
+        assert_eq!(locale["Shift"], "Umschalt");
+        assert_eq!(locale["home"], "anfang");
+        assert!(!locale.contains_key("language"));
+    }
+
+    #[test]
+    fn in_memory_config_has_defaults_and_no_file() {
+        let config = Config::in_memory();
+        assert!(config.reload_rx.is_none() && config.last_modified.is_none());
+        assert!(!config.hot_reload);
+        assert!(config.file().is_none());
+        assert!(matches!(config.save(), Err(FerrisKeysError::NoConfigFile)));
+
+        let mut loaded = Config::load(Path::new("does-not-exist.toml")).unwrap();
+        assert_ne!(config, loaded);
+        loaded.hot_reload = false;
+        assert_eq!(config, loaded);
+    }
+
+    #[test]
+    fn unwritable_config_dir_falls_back_to_defaults() {
+        // A file where the config folder should be makes creating it fail on every platform
+        let blocker = TempPath::new("tmp");
+        fs::write(&*blocker, "").unwrap();
+        let path = blocker.join("config.toml");
+
+        assert!(matches!(
+            Config::ensure_config_exists(Some(&path)),
+            Err(FerrisKeysError::Io { .. })
+        ));
+        assert_eq!(
+            Config::load_auto(Some(&path)).timeout_ms,
+            Config::default().timeout_ms
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_config_path_is_created_where_it_is_loaded() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = TempPath::new(OsStr::from_bytes(b"t\xffml"));
+        Config::ensure_config_exists(Some(&path)).unwrap();
+
+        assert!(path.exists());
+        assert_eq!(Config::active_path(Some(&path)).as_deref(), Some(&*path));
+    }
+
+    #[test]
+    fn margin_axes_override_the_shared_margin() {
+        with_temp_config("[window]\nmargin = 10.0\nmargin_y = 40.0\n", |path| {
+            let config = Config::load(path).unwrap();
+            assert_eq!(config.margin, [10.0, 40.0]);
+            assert_eq!(Config::default().margin, [16.0, 16.0]);
+        });
+    }
+
+    #[test]
+    fn negative_max_fps_is_rejected() {
+        let table: Value = toml::from_str("max_fps = -30").unwrap();
+        assert_eq!(Config::from_toml(Some(&table), Path::new("")).max_fps, 0);
+
+        let table: Value = toml::from_str("max_fps = 60").unwrap();
+        assert_eq!(Config::from_toml(Some(&table), Path::new("")).max_fps, 60);
+    }
+
+    #[test]
+    fn window_borders_turn_off_click_through() {
+        assert!(Config::default().mouse_passthrough());
+
+        let table: Value = toml::from_str("[window]\ndecorations = true").unwrap();
+        assert!(!Config::from_toml(Some(&table), Path::new("")).mouse_passthrough());
+    }
+
+    #[test]
+    fn grab_modifier_accepts_modifier_names_and_aliases() {
+        let grab = |value: &str| {
+            let table: Value =
+                toml::from_str(&format!("[window]\ngrab_modifier = \"{value}\"")).unwrap();
+            Config::from_toml(Some(&table), Path::new("")).grab_modifier
+        };
+        assert_eq!(grab("super"), Some(Modifier::Meta));
+        assert_eq!(grab("Ctrl"), Some(Modifier::Control));
+        assert_eq!(grab("none"), None);
+        assert_eq!(grab("hyper"), None);
+        assert_eq!(Config::default().grab_modifier, None);
+    }
+
+    #[test]
+    fn panel_content_padding_defaults_to_padding() {
+        let table: Value = toml::from_str("padding = 6.0").unwrap();
+        assert_eq!(parse_panel(&table).content_padding, 6.0);
+
+        let table: Value = toml::from_str("padding = 6.0\ncontent_padding = 20.0").unwrap();
+        let panel = parse_panel(&table);
+        assert_eq!((panel.padding, panel.content_padding), (6.0, 20.0));
+    }
+
+    #[test]
+    fn aspect_ratio_derives_height_from_width() {
+        let table: Value =
+            toml::from_str("width = 120.0\nheight = 10.0\naspect_ratio = \"4:3\"").unwrap();
+        assert_eq!(parse_style(&table, &KeyCategory::Normal).height, 90.0);
+
+        let table: Value = toml::from_str("width = 80.0\naspect_ratio = \"square\"").unwrap();
+        assert_eq!(parse_style(&table, &KeyCategory::Normal).height, 80.0);
+
+        let table: Value = toml::from_str("width = 80.0\nheight = 50.0\naspect_ratio = 0").unwrap();
+        assert_eq!(parse_style(&table, &KeyCategory::Normal).height, 50.0);
+    }
+
+    #[test]
+    fn disabling_hot_reload_ignores_edits() {
+        with_temp_config("hot_reload = false\ntimeout_ms = 100\n", |path| {
+            let mut config = Config::load(path).unwrap();
+            assert!(config.reload_rx.is_none());
+
+            fs::write(path, "hot_reload = false\ntimeout_ms = 200\n").unwrap();
+            assert!(!config.maybe_reload());
+            assert_eq!(config.timeout_ms, 100);
+        });
+    }
+}
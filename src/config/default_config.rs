@@ -1,10 +1,106 @@
 pub const DEFAULT_CONFIG_TOML: &str = r###"
 timeout_ms = 1200
+double_click_ms = 400
+debounce_ms = 250
+show_mouse_drag = false
+show_unknown = true  # set to false to hide keys with no known category (e.g. "Unknown(123)")
+show_numpad_grid = false  # show numpad keys in a numpad-shaped grid on the left instead of the key row
+log_unmapped = false  # record unknown keys to unmapped.log next to this file, to help add mappings
+sticky = false
+modifiers_only_in_chords = false
+trigger = "always"  # or "modifier_held" to show keys only while a modifier is down
+empty_label = "placeholder"  # or "skip" to hide keys whose label is blank
+sort_simultaneous = "time"  # or "modifier_first" to show keys pressed together as Ctrl, Shift, Alt, Meta, then the rest
+display_mode = "keys"  # or "caption" for a subtitle-style bar of the typed text
+group_text = false  # show quickly typed text as one entry ("hello") instead of one box per key
+group_text_ms = 600  # longest pause between keys that still continues the typed text
+only_when_focused = []  # e.g. ["code", "vim"] to show keys only while a matching window is focused
+show_capture_indicator = false  # small red dot while keys are captured, grey while paused or not capturing
+clear_hotkey = ""  # e.g. "ctrl+shift+k" to wipe the overlay
+theme_cycle = []  # e.g. ["dark", "light", "nord"] to switch between with theme_cycle_hotkey
+theme_cycle_hotkey = ""  # e.g. "ctrl+shift+t" to apply the next theme in theme_cycle
+screenshot_hotkey = ""  # e.g. "ctrl+shift+p" to save the overlay as a PNG next to this file
+settings_hotkey = ""  # e.g. "ctrl+shift+o" to open the settings window
+fixed_slots = 0
+max_fps = 0
+auto_width = false  # size every key box to its label instead of the style's fixed width
+render_mode = "continuous"  # or "reactive" to use less CPU when idle
+max_animation_step_ms = 50  # longest time one slow frame may advance animations by (0 = no limit)
+reload_interval_ms = 500  # minimum gap between config hot-reloads
+hot_reload = true  # set to false to apply edits only on restart
+watch_content = false  # also detect edits by file contents, not just mtime
+# layout = "us"  # override keyboard layout detection ("us", "gb", "es", "it", "se", "no", "dk")
+show_layout = false  # print and display the keyboard layout in use
+compose = false  # Linux: show Compose sequences (Compose, ', e) as the character they type (é)
+glyphs = true  # set to false if icons show up as empty boxes
+compact = false  # icon and label on one line, for shorter boxes
+pixel_snap = false  # round boxes and text to whole pixels; crisper on low-DPI screens, less smooth animation
+row_align = "bottom"  # "top", "center", or "bottom" for mixed-height keys
+entry_animation = "scale"  # "none", "scale", "fade", "slide", or "pop"
+exit_animation = "none"  # same choices, played when a key expires
+highlight_latest = false  # accent the newest key with a tint and border
+highlight_color = "#ffcc00"
+highlight_border = 2.0
+pulse_held = false  # pulse keys while they're held down, e.g. Ctrl during a long shortcut
+velocity_visualization = false  # draw keys pressed in quick succession brighter and larger
+overflow = "trim"  # "trim" drops keys that don't fit, "scroll" keeps them and slides the row
+modifier_style = "both"  # "glyph", "word", or "both"
 
 [window]
 monitor = 0
 position = [500.0, 500.0]
+position_relative_to = "desktop"
+# anchor = "bottom-center"  # remove `position` to use an anchor instead
+margin = 16.0  # or margin_x / margin_y to set each direction separately
 size = [800, 120]
+max_content_width = 0.0  # cap the key row's width inside a larger window (0 = no cap)
+max_content_height = 0.0  # likewise for its height
+mirror_all_monitors = false
+follow_cursor = false  # move the overlay next to the mouse cursor, e.g. for pen demos
+always_on_top = true
+skip_taskbar = true  # hide from the taskbar and Alt-Tab on Windows and X11
+decorations = false
+resizable = false
+blur_background = false  # blur what is behind the overlay (KDE Plasma on X11/XWayland, Windows)
+grab_modifier = "none"  # e.g. "super": hold it to drag the otherwise click-through overlay
+title = "FerrisKeys"
+app_id = "ferriskeys"
+
+[sound]
+enabled = false
+file = ""
+
+# Replace the text shown for specific keys, e.g. `Space = "⎵"` or `Return = "↵ Return"`
+# (text before the first space is drawn as the icon)
+[labels]
+
+# Uncomment to show key names such as "shift" or "home" in another language
+# [locale]
+# language = "de"  # bundled names for "de", "fr" or "es"
+# pgup = "bild hoch"  # translate (or re-translate) single words
+
+# Uncomment to draw a drop shadow behind every key box
+# (add a `shadow` table to a single `[styles.*]` entry to style just that category)
+# [shadow]
+# offset = [4.0, 4.0]
+# blur = 6.0
+# color = "#000000"
+# opacity = 0.5
+
+# Uncomment to outline key text so it stays readable over bright backgrounds
+# (a `text_outline` table in a `[styles.*]` entry applies to just that category)
+# [text_outline]
+# width = 1.5
+# color = "#000000"
+# opacity = 1.0
+
+# Uncomment to draw a translucent rounded panel behind the keys
+# [panel]
+# color = "#000000"
+# opacity = 0.4
+# padding = 8.0
+# content_padding = 8.0  # gap between the panel's sides and the first/last key
+# rounding = 12.0
 
 [styles.normal]
 width = 90.0
@@ -0,0 +1,312 @@
+//! Writing the effective config back out as a complete config file, for `--dump-config`.
+//!
+//! The TOML mirrors `config.toml`'s layout (`[window]`, `[styles.*]`, ...) rather than the
+//! flat `Config` struct, so the output loads back to the same settings.
+
+use crate::config::config::{
+    category_name, rounded, to_hex, Anchor, Animation, Config, DisplayMode, EmptyLabel,
+    GradientDirection, Hotkey, IconPosition, Modifier, ModifierStyle, Overflow, Panel,
+    PositionOrigin, RenderMode, RowAlign, Shadow, SortSimultaneous, SoundConfig, Style,
+    TextOutline, Trigger,
+};
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+impl Config {
+    /// Returns every setting as TOML, i.e. the file's values with every fallback filled in.
+    /// Used by `--dump-config`.
+    ///
+    /// Runtime state (the file path, reload timestamps) isn't a setting and is left out.
+    ///
+    /// # Errors
+    /// Returns an error if a value can't be written as TOML.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(&ConfigFile::new(self))
+    }
+}
+
+/// The top level of `config.toml`.
+#[derive(Serialize)]
+struct ConfigFile<'a> {
+    timeout_ms: u64,
+    double_click_ms: u64,
+    debounce_ms: u64,
+    show_mouse_drag: bool,
+    show_unknown: bool,
+    show_numpad_grid: bool,
+    log_unmapped: bool,
+    sticky: bool,
+    modifiers_only_in_chords: bool,
+    trigger: Trigger,
+    empty_label: EmptyLabel,
+    sort_simultaneous: SortSimultaneous,
+    display_mode: DisplayMode,
+    group_text: bool,
+    group_text_ms: u64,
+    only_when_focused: &'a [String],
+    show_capture_indicator: bool,
+    clear_hotkey: Option<&'a Hotkey>,
+    theme_cycle: &'a [String],
+    theme_cycle_hotkey: Option<&'a Hotkey>,
+    screenshot_hotkey: Option<&'a Hotkey>,
+    settings_hotkey: Option<&'a Hotkey>,
+    fixed_slots: usize,
+    max_fps: u64,
+    render_mode: RenderMode,
+    max_animation_step_ms: u64,
+    reload_interval_ms: u64,
+    hot_reload: bool,
+    watch_content: bool,
+    layout: Option<&'static str>,
+    show_layout: bool,
+    compose: bool,
+    glyphs: bool,
+    compact: bool,
+    pixel_snap: bool,
+    row_align: RowAlign,
+    entry_animation: Animation,
+    exit_animation: Animation,
+    highlight_latest: bool,
+    highlight_color: String,
+    highlight_border: f64,
+    pulse_held: bool,
+    velocity_visualization: bool,
+    overflow: Overflow,
+    modifier_style: ModifierStyle,
+    window: WindowTable<'a>,
+    sound: &'a SoundConfig,
+    labels: BTreeMap<&'a str, &'a str>,
+    locale: BTreeMap<&'a str, &'a str>,
+    panel: Option<PanelTable>,
+    styles: BTreeMap<&'static str, StyleTable>,
+}
+
+/// The `[window]` table.
+#[derive(Serialize)]
+struct WindowTable<'a> {
+    monitor: usize,
+    // Left out with an anchor, since a position always wins over it
+    position: Option<[f64; 2]>,
+    position_relative_to: PositionOrigin,
+    anchor: Option<Anchor>,
+    margin_x: f64,
+    margin_y: f64,
+    size: [f64; 2],
+    max_content_width: f64,
+    max_content_height: f64,
+    mirror_all_monitors: bool,
+    follow_cursor: bool,
+    always_on_top: bool,
+    skip_taskbar: bool,
+    decorations: bool,
+    resizable: bool,
+    blur_background: bool,
+    grab_modifier: Option<Modifier>,
+    title: &'a str,
+    app_id: &'a str,
+}
+
+/// One `[styles.*]` table.
+#[derive(Serialize)]
+struct StyleTable {
+    width: f64,
+    min_width: Option<f64>,
+    max_width: Option<f64>,
+    auto_width: bool,
+    height: f64,
+    icon_size: f64,
+    text_size: f64,
+    bg_color: String,
+    fg_color: String,
+    bg_color2: Option<String>,
+    gradient_direction: GradientDirection,
+    icon_position: IconPosition,
+    shadow: Option<ShadowTable>,
+    text_outline: Option<TextOutlineTable>,
+}
+
+/// A style's `shadow` table. The color keeps its opacity, so `opacity` is always 1.
+#[derive(Serialize)]
+struct ShadowTable {
+    offset: [f64; 2],
+    blur: f64,
+    color: String,
+    opacity: f64,
+}
+
+/// A style's `text_outline` table, with the opacity kept in the color like `ShadowTable`.
+#[derive(Serialize)]
+struct TextOutlineTable {
+    width: f64,
+    color: String,
+    opacity: f64,
+}
+
+/// The `[panel]` table, with the opacity kept in the color like `ShadowTable`.
+#[derive(Serialize)]
+struct PanelTable {
+    color: String,
+    opacity: f64,
+    padding: f64,
+    content_padding: f64,
+    rounding: f64,
+}
+
+impl<'a> ConfigFile<'a> {
+    fn new(config: &'a Config) -> Self {
+        ConfigFile {
+            timeout_ms: config.timeout_ms,
+            double_click_ms: config.double_click_ms,
+            debounce_ms: config.debounce_ms,
+            show_mouse_drag: config.show_mouse_drag,
+            show_unknown: config.show_unknown,
+            show_numpad_grid: config.show_numpad_grid,
+            log_unmapped: config.log_unmapped,
+            sticky: config.sticky,
+            modifiers_only_in_chords: config.modifiers_only_in_chords,
+            trigger: config.trigger,
+            empty_label: config.empty_label,
+            sort_simultaneous: config.sort_simultaneous,
+            display_mode: config.display_mode,
+            group_text: config.group_text,
+            group_text_ms: config.group_text_ms,
+            only_when_focused: &config.only_when_focused,
+            show_capture_indicator: config.show_capture_indicator,
+            clear_hotkey: config.clear_hotkey.as_ref(),
+            theme_cycle: &config.theme_cycle,
+            theme_cycle_hotkey: config.theme_cycle_hotkey.as_ref(),
+            screenshot_hotkey: config.screenshot_hotkey.as_ref(),
+            settings_hotkey: config.settings_hotkey.as_ref(),
+            fixed_slots: config.fixed_slots,
+            max_fps: config.max_fps,
+            render_mode: config.render_mode,
+            max_animation_step_ms: config.max_animation_step_ms,
+            reload_interval_ms: config.reload_interval_ms,
+            hot_reload: config.hot_reload,
+            watch_content: config.watch_content,
+            layout: config.layout.and_then(|layout| layout.name()),
+            show_layout: config.show_layout,
+            compose: config.compose,
+            glyphs: config.glyphs,
+            compact: config.compact,
+            pixel_snap: config.pixel_snap,
+            row_align: config.row_align,
+            entry_animation: config.entry_animation,
+            exit_animation: config.exit_animation,
+            highlight_latest: config.highlight_latest,
+            highlight_color: to_hex(config.highlight_color),
+            highlight_border: rounded(config.highlight_border),
+            pulse_held: config.pulse_held,
+            velocity_visualization: config.velocity_visualization,
+            overflow: config.overflow,
+            modifier_style: config.modifier_style,
+            window: WindowTable::new(config),
+            sound: &config.sound,
+            labels: config
+                .labels
+                .iter()
+                .map(|(key, text)| (key.as_str(), text.as_str()))
+                .collect(),
+            // Each word is also stored capitalized; the lowercase entry brings both back
+            locale: config
+                .locale
+                .iter()
+                .filter(|(word, _)| word.to_lowercase() == **word)
+                .map(|(word, text)| (word.as_str(), text.as_str()))
+                .collect(),
+            panel: config.panel.as_ref().map(PanelTable::new),
+            styles: config
+                .styles
+                .iter()
+                .map(|(category, style)| (category_name(category), StyleTable::new(style)))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> WindowTable<'a> {
+    fn new(config: &'a Config) -> Self {
+        WindowTable {
+            monitor: config.monitor,
+            position: config.anchor.is_none().then(|| pair(config.position)),
+            position_relative_to: config.position_relative_to,
+            anchor: config.anchor,
+            margin_x: rounded(config.margin[0]),
+            margin_y: rounded(config.margin[1]),
+            size: pair(config.size),
+            max_content_width: rounded(config.max_content_width),
+            max_content_height: rounded(config.max_content_height),
+            mirror_all_monitors: config.mirror_all_monitors,
+            follow_cursor: config.follow_cursor,
+            always_on_top: config.always_on_top,
+            skip_taskbar: config.skip_taskbar,
+            decorations: config.decorations,
+            resizable: config.resizable,
+            blur_background: config.blur_background,
+            grab_modifier: config.grab_modifier,
+            title: &config.window_title,
+            app_id: &config.app_id,
+        }
+    }
+}
+
+impl StyleTable {
+    fn new(style: &Style) -> Self {
+        StyleTable {
+            width: rounded(style.width),
+            min_width: style.min_width.map(rounded),
+            max_width: style.max_width.map(rounded),
+            auto_width: style.auto_width,
+            height: rounded(style.height),
+            icon_size: rounded(style.icon_size),
+            text_size: rounded(style.text_size),
+            bg_color: to_hex(style.bg_color),
+            fg_color: to_hex(style.fg_color),
+            bg_color2: style.bg_color2.map(to_hex),
+            gradient_direction: style.gradient_direction,
+            icon_position: style.icon_position,
+            shadow: style.shadow.as_ref().map(ShadowTable::new),
+            text_outline: style.text_outline.as_ref().map(TextOutlineTable::new),
+        }
+    }
+}
+
+impl ShadowTable {
+    fn new(shadow: &Shadow) -> Self {
+        ShadowTable {
+            offset: pair(shadow.offset),
+            blur: rounded(shadow.blur),
+            color: to_hex(shadow.color),
+            opacity: 1.0,
+        }
+    }
+}
+
+impl TextOutlineTable {
+    fn new(outline: &TextOutline) -> Self {
+        TextOutlineTable {
+            width: rounded(outline.width),
+            color: to_hex(outline.color),
+            opacity: 1.0,
+        }
+    }
+}
+
+impl PanelTable {
+    fn new(panel: &Panel) -> Self {
+        PanelTable {
+            color: to_hex(panel.color),
+            opacity: 1.0,
+            padding: rounded(panel.padding),
+            content_padding: rounded(panel.content_padding),
+            rounding: rounded(panel.rounding),
+        }
+    }
+}
+
+/// Rounds a pair of floats, e.g. a position or size.
+fn pair(values: [f32; 2]) -> [f64; 2] {
+    values.map(rounded)
+}
@@ -0,0 +1,73 @@
+//! Bundled translations of key names, picked with `language` in the `[locale]` table.
+//!
+//! Only the word part of a label is translated (e.g. `shift` in `⇧ shift`); glyphs, letters
+//! and symbols are shown as-is. Words are lowercase here; see `Config::locale` for how
+//! capitalized labels such as `Shift` are matched.
+
+/// German key names, as printed on German keyboards.
+const GERMAN: &[(&str, &str)] = &[
+    ("shift", "umschalt"),
+    ("control", "strg"),
+    ("caps", "feststell"),
+    ("enter", "eingabe"),
+    ("space", "leer"),
+    ("back", "rück"),
+    ("del", "entf"),
+    ("ins", "einfg"),
+    ("home", "pos1"),
+    ("end", "ende"),
+    ("pgup", "bild↑"),
+    ("pgdn", "bild↓"),
+    ("numlock", "num"),
+    ("scroll", "rollen"),
+    ("ps", "druck"),
+];
+
+/// French key names, as printed on French keyboards.
+const FRENCH: &[(&str, &str)] = &[
+    ("shift", "maj"),
+    ("control", "ctrl"),
+    ("caps", "verr maj"),
+    ("enter", "entrée"),
+    ("space", "espace"),
+    ("back", "retour"),
+    ("esc", "échap"),
+    ("del", "suppr"),
+    ("ins", "inser"),
+    ("home", "début"),
+    ("end", "fin"),
+    ("pgup", "pg préc"),
+    ("pgdn", "pg suiv"),
+    ("numlock", "verr num"),
+    ("scroll", "arrêt défil"),
+    ("ps", "impr"),
+];
+
+/// Spanish key names, as printed on Spanish keyboards.
+const SPANISH: &[(&str, &str)] = &[
+    ("shift", "mayús"),
+    ("control", "ctrl"),
+    ("caps", "bloq mayús"),
+    ("enter", "intro"),
+    ("space", "espacio"),
+    ("back", "retroceso"),
+    ("del", "supr"),
+    ("home", "inicio"),
+    ("end", "fin"),
+    ("pgup", "re pág"),
+    ("pgdn", "av pág"),
+    ("numlock", "bloq num"),
+    ("scroll", "bloq despl"),
+    ("ps", "impr pant"),
+];
+
+/// Returns the bundled `(word, translation)` pairs for a language code (`de`, `fr`, `es`),
+/// ignoring case.
+pub fn preset(language: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    match language.to_ascii_lowercase().as_str() {
+        "de" => Some(GERMAN),
+        "fr" => Some(FRENCH),
+        "es" => Some(SPANISH),
+        _ => None,
+    }
+}
@@ -1,2 +1,5 @@
 pub mod config;
 pub mod default_config;
+pub mod dump;
+pub mod locale;
+pub mod theme;
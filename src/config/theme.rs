@@ -0,0 +1,94 @@
+//! Built-in color themes that `theme_cycle` switches between at runtime.
+
+use crate::config::config::Style;
+use crate::input::keymap::KeyCategory;
+
+use std::collections::HashMap;
+
+use eframe::egui::Color32;
+
+/// A named set of key colors, applied over the configured styles (sizes are kept).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub bg_color: Color32,     // Background of ordinary keys
+    pub fg_color: Color32,     // Label color of every key
+    pub accent_color: Color32, // Background of modifiers, Escape, function keys and the mouse
+}
+
+/// Every built-in theme, by name.
+pub const THEMES: [Theme; 5] = [
+    Theme {
+        name: "dark",
+        bg_color: Color32::from_rgb(0x2e, 0x2e, 0x2e),
+        fg_color: Color32::from_rgb(0xff, 0xff, 0xff),
+        accent_color: Color32::from_rgb(0x44, 0x44, 0x44),
+    },
+    Theme {
+        name: "light",
+        bg_color: Color32::from_rgb(0xf2, 0xf2, 0xf2),
+        fg_color: Color32::from_rgb(0x1e, 0x1e, 0x1e),
+        accent_color: Color32::from_rgb(0xd0, 0xd4, 0xdc),
+    },
+    Theme {
+        name: "nord",
+        bg_color: Color32::from_rgb(0x3b, 0x42, 0x52),
+        fg_color: Color32::from_rgb(0xec, 0xef, 0xf4),
+        accent_color: Color32::from_rgb(0x5e, 0x81, 0xac),
+    },
+    Theme {
+        name: "solarized",
+        bg_color: Color32::from_rgb(0x07, 0x36, 0x42),
+        fg_color: Color32::from_rgb(0xee, 0xe8, 0xd5),
+        accent_color: Color32::from_rgb(0x26, 0x8b, 0xd2),
+    },
+    Theme {
+        name: "dracula",
+        bg_color: Color32::from_rgb(0x28, 0x2a, 0x36),
+        fg_color: Color32::from_rgb(0xf8, 0xf8, 0xf2),
+        accent_color: Color32::from_rgb(0x62, 0x72, 0xa4),
+    },
+];
+
+impl Theme {
+    /// Looks up a built-in theme by name, ignoring case.
+    pub fn named(name: &str) -> Option<Theme> {
+        THEMES
+            .iter()
+            .find(|theme| theme.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// Recolors every style with this theme, dropping any gradient.
+    pub fn apply(&self, styles: &mut HashMap<KeyCategory, Style>) {
+        use KeyCategory::*;
+
+        for (category, style) in styles.iter_mut() {
+            style.bg_color = match category {
+                Modifier | Escape | Function | AltFunction | Mouse => self.accent_color,
+                _ => self.bg_color,
+            };
+            style.fg_color = self.fg_color;
+            style.bg_color2 = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::config::Config;
+
+    #[test]
+    fn applying_a_theme_recolors_styles_by_category() {
+        let nord = Theme::named("Nord").unwrap();
+        let mut styles = Config::fallback_styles();
+        let width = styles[&KeyCategory::Normal].width;
+        nord.apply(&mut styles);
+
+        assert_eq!(styles[&KeyCategory::Normal].bg_color, nord.bg_color);
+        assert_eq!(styles[&KeyCategory::Modifier].bg_color, nord.accent_color);
+        assert_eq!(styles[&KeyCategory::Normal].width, width);
+        assert!(Theme::named("neon").is_none());
+    }
+}
@@ -0,0 +1,1092 @@
+use crate::config::config::{Animation, Config, EmptyLabel, ModifierStyle, Overflow, Style};
+use crate::core::wake::wake;
+use crate::input::input::KeyId;
+use crate::input::keymap::{
+    category_for_key, chord_modifier, format_modifier_label, glyph_word, normalize_key_label,
+    normalize_mouse_label, KeyCategory,
+};
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Represents a single key or mouse input event for visualization.
+#[derive(Clone)]
+pub struct KeyEntry {
+    pub icon: String,      // Optional icon string (e.g., modifier or mouse icon)
+    pub label: String,     // Main label text (e.g., "Ctrl", "A", "F5")
+    pub anim: f32,         // Animation progress (0.0 to 1.0)
+    pub time: Instant,     // Time of last event (for fading/removal)
+    pub exit: Option<f32>, // Exit animation progress (0.0 to 1.0) once expired
+    pub id: Option<KeyId>, // Physical key shown, if known (see `push_key`)
+    pub grouped: bool,     // Typed text run built up by `group_text`
+    pub held: bool,        // Whether the physical key is still down (cleared by `release`)
+    pub pulse: f32,        // Strength of the `pulse_held` effect: 1.0 while held, decaying after
+    pub text_width: f32,   // Measured width of the icon and label as drawn (0 until rendered)
+    pub velocity: f32,     // How quickly it followed the previous press: 0.0 (slow) to 1.0 (fast)
+}
+
+impl KeyEntry {
+    /// Advances the entry animation by `frames` (see `ANIMATION_FRAME`), up to fully shown,
+    /// and the exit animation (if leaving) up to fully gone. The held pulse fades out once
+    /// released.
+    pub fn advance_animation(&mut self, frames: f32) {
+        if self.anim < 1.0 {
+            self.anim += 0.1 * frames;
+        }
+        if self.held {
+            self.pulse = 1.0;
+        } else if self.pulse > 0.0 {
+            self.pulse *= 0.85_f32.powf(frames);
+            if self.pulse < 0.01 {
+                self.pulse = 0.0;
+            }
+        }
+        if let Some(exit) = &mut self.exit {
+            *exit = (*exit + 0.1 * frames).min(1.0);
+        }
+    }
+}
+
+/// The frame every animation step is tuned for (60fps); slower or faster frames advance
+/// animations by proportionally more or less.
+pub const ANIMATION_FRAME: Duration = Duration::from_micros(16_667);
+
+/// Entry animation progress a new key starts at.
+///
+/// `Scale` keeps the original subtle grow-in from 80%; the others play in full.
+fn entry_start(animation: Animation) -> f32 {
+    match animation {
+        Animation::None => 1.0,
+        Animation::Scale => 0.8,
+        Animation::Fade | Animation::Slide | Animation::Pop => 0.0,
+    }
+}
+
+/// Label shown for keys with a blank label, with `empty_label = "placeholder"`.
+const EMPTY_PLACEHOLDER: &str = "?";
+
+/// Presses at most this far apart count as full velocity.
+const FAST_PRESS: Duration = Duration::from_millis(60);
+
+/// Presses at least this far apart count as zero velocity.
+const SLOW_PRESS: Duration = Duration::from_millis(500);
+
+/// Maps the time since the previous press to a velocity for `velocity_visualization`,
+/// from 0.0 (`SLOW_PRESS` or slower) up to 1.0 (`FAST_PRESS` or faster).
+pub fn press_velocity(interval: Duration) -> f32 {
+    let span = (SLOW_PRESS - FAST_PRESS).as_secs_f32();
+    let over = interval.saturating_sub(FAST_PRESS).as_secs_f32();
+    (1.0 - over / span).clamp(0.0, 1.0)
+}
+
+/// Horizontal gap after each key box, in pixels.
+pub const KEY_PADDING: f32 = 8.0;
+
+/// Most keys kept with `overflow = "scroll"`, so the history can't grow without bound.
+pub const SCROLLBACK_LIMIT: usize = 500;
+
+/// A ring buffer of currently visible keys to render onscreen.
+///
+/// Used to store and display a limited set of recent inputs. When `fixed_slots` is
+/// enabled, keys live in positional `slots` instead, and expired ones are blanked in
+/// place so the remaining keys never shift.
+pub struct KeyBuffer {
+    pub keys: VecDeque<KeyEntry>,
+    pub slots: Vec<Option<KeyEntry>>,
+    /// How far right the row is still shifted while a new key slides in (`overflow = "scroll"`).
+    pub scroll: f32,
+    /// Set when a key arrives while nothing animates, so the next animation step counts as
+    /// one frame rather than however long the overlay sat still.
+    pub restart_animation: bool,
+}
+
+impl Default for KeyBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyBuffer {
+    /// Creates a new, empty key buffer.
+    pub fn new() -> Self {
+        Self {
+            keys: VecDeque::new(),
+            slots: Vec::new(),
+            scroll: 0.0,
+            restart_animation: false,
+        }
+    }
+
+    /// Returns `true` if no keys are currently held for display.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.slots.iter().all(Option::is_none)
+    }
+
+    /// Returns `true` while the overlay still changes on its own, so it must keep repainting:
+    /// a key is entering or leaving, a held key pulses, the row is sliding, or (unless
+    /// `sticky`) shown keys are still waiting for `timeout_ms` to pass (see `expire`).
+    ///
+    /// Once this is `false`, nothing moves until the next input event.
+    pub fn is_animating(&self, config: &Config) -> bool {
+        let mut entries = self.keys.iter().chain(self.slots.iter().flatten());
+        let moving = entries.any(|k| {
+            k.anim < 1.0 || k.exit.is_some() || (config.pulse_held && (k.held || k.pulse > 0.0))
+        });
+        moving || self.scroll > 0.0 || (!config.sticky && !self.is_empty())
+    }
+
+    /// Returns `true` once keys are shown and every one has finished animating in.
+    pub fn is_fully_shown(&self) -> bool {
+        let mut entries = self
+            .keys
+            .iter()
+            .chain(self.slots.iter().flatten())
+            .peekable();
+        entries.peek().is_some() && entries.all(|k| k.anim >= 1.0 && k.exit.is_none())
+    }
+
+    /// Resizes the positional slots; `0` switches back to the collapsing ring buffer.
+    pub fn set_slot_count(&mut self, count: usize) {
+        if count == 0 {
+            self.slots.clear();
+        } else {
+            self.slots.resize(count, None);
+        }
+    }
+
+    /// Removes every displayed key at once.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+    }
+
+    /// Clears the buffer if the newest key is older than `after`.
+    ///
+    /// Used by sticky mode so a fresh press replaces the previous chord rather than
+    /// appending to it, while keys pressed in quick succession still group together.
+    pub fn clear_if_settled(&mut self, after: Duration) {
+        if self.keys.back().is_some_and(|k| k.time.elapsed() > after) {
+            self.keys.clear();
+        }
+
+        let newest = self.slots.iter().flatten().map(|k| k.time).max();
+        if newest.is_some_and(|t| t.elapsed() > after) {
+            self.slots.iter_mut().for_each(|slot| *slot = None);
+        }
+    }
+
+    /// Adds a new key or mouse input to the buffer.
+    ///
+    /// - Prevents duplicate key labels by refreshing existing ones.
+    /// - With an `id`, also refreshes the entry for the same physical key, taking on the
+    ///   new label, so toggling Shift on one key doesn't spawn "a", "A" and "@" entries.
+    /// - Formats the label with `format_label`.
+    /// - With `group_text`, typed characters join the newest run, and Backspace takes
+    ///   back its last character instead of showing a box.
+    pub fn push_key(&mut self, id: Option<&KeyId>, label: &str, mouse: bool, config: &Config) {
+        self.push_key_with_velocity(id, label, mouse, 0.0, config);
+    }
+
+    /// Like `push_key`, but records how fast the key was pressed (see `press_velocity`)
+    /// on the new or refreshed entry, for `velocity_visualization`.
+    pub fn push_key_with_velocity(
+        &mut self,
+        id: Option<&KeyId>,
+        label: &str,
+        mouse: bool,
+        velocity: f32,
+        config: &Config,
+    ) {
+        if !self.is_animating(config) {
+            self.restart_animation = true;
+        }
+        if config.group_text
+            && !mouse
+            && normalize_key_label(label) == normalize_key_label("Backspace")
+            && self.pop_typed_char(config)
+        {
+            return;
+        }
+
+        // Check if label already exists and refresh its time/animation if found
+        if let Some(existing) = self
+            .keys
+            .iter_mut()
+            .chain(self.slots.iter_mut().flatten())
+            .find(|k| k.label == label && !k.grouped)
+        {
+            existing.time = Instant::now();
+            existing.exit = None;
+            existing.held |= id.is_some();
+            existing.velocity = velocity;
+            if config.entry_animation == Animation::Scale {
+                existing.anim = 0.8;
+            }
+            return;
+        }
+
+        let (icon, mut formatted_label) = format_label(label, mouse, config);
+
+        // A blank label would draw an empty box: skip the key or show a placeholder
+        if icon.trim().is_empty() && formatted_label.trim().is_empty() {
+            match config.empty_label {
+                EmptyLabel::Skip => return,
+                EmptyLabel::Placeholder => formatted_label = EMPTY_PLACEHOLDER.to_string(),
+            }
+        }
+
+        // With `group_text`, typed characters join the newest entry while typing continues
+        let text = typed_text(&icon, &formatted_label).filter(|_| config.group_text && !mouse);
+        if let Some(text) = &text {
+            let window = Duration::from_millis(config.group_text_ms);
+            if let Some(group) = self
+                .newest_mut()
+                .filter(|k| k.grouped && k.exit.is_none() && k.time.elapsed() <= window)
+            {
+                group.label.push_str(text);
+                group.time = Instant::now();
+                group.velocity = velocity;
+                return;
+            }
+        }
+
+        // The same physical key under a different label replaces its entry in place
+        if let Some(existing) = self
+            .keys
+            .iter_mut()
+            .chain(self.slots.iter_mut().flatten())
+            .find(|k| id.is_some() && !k.grouped && k.id.as_ref() == id)
+        {
+            existing.icon = icon;
+            existing.label = formatted_label;
+            existing.time = Instant::now();
+            existing.exit = None;
+            existing.held = true;
+            existing.velocity = velocity;
+            if config.entry_animation == Animation::Scale {
+                existing.anim = 0.8;
+            }
+            return;
+        }
+
+        // Add the newly created entry to the buffer; the first typed character starts a group
+        let entry = match text {
+            Some(text) => KeyEntry {
+                icon: String::new(),
+                label: text,
+                anim: entry_start(config.entry_animation),
+                time: Instant::now(),
+                exit: None,
+                id: None,
+                grouped: true,
+                held: false,
+                pulse: 0.0,
+                text_width: 0.0,
+                velocity,
+            },
+            None => KeyEntry {
+                icon,
+                label: formatted_label,
+                anim: entry_start(config.entry_animation),
+                time: Instant::now(),
+                exit: None,
+                id: id.cloned(),
+                grouped: false,
+                held: id.is_some(),
+                pulse: 0.0,
+                text_width: 0.0,
+                velocity,
+            },
+        };
+
+        if self.slots.is_empty() {
+            // In scroll mode the row slides left by the new key's width instead of jumping
+            if config.overflow == Overflow::Scroll {
+                self.scroll += entry_style(config, &entry).width + KEY_PADDING;
+            }
+            self.keys.push_back(entry);
+        } else {
+            self.insert_into_slot(entry);
+        }
+    }
+
+    /// Shows a double click by turning the left click that started it into the "double"
+    /// box, so it reads as one double click rather than a click followed by a double.
+    ///
+    /// If a "double" box is already shown (a second double click), it is refreshed and the
+    /// click dropped instead, like a repeated key. Without either, the double is pushed.
+    pub fn push_double_click(&mut self, config: &Config) {
+        let left = format_label("MouseLeft", true, config);
+        let double = format_label("MouseDouble", true, config);
+        let shows = |k: &KeyEntry, (icon, label): &(String, String)| {
+            !k.grouped && k.icon == *icon && k.label == *label
+        };
+
+        // A second double click refreshes the "double" box, and its click is dropped
+        let repeat = self
+            .keys
+            .iter()
+            .chain(self.slots.iter().flatten())
+            .any(|k| shows(k, &double));
+        if repeat {
+            self.keys.retain(|k| !shows(k, &left));
+            for slot in &mut self.slots {
+                if slot.as_ref().is_some_and(|k| shows(k, &left)) {
+                    *slot = None;
+                }
+            }
+        }
+
+        let target = if repeat { &double } else { &left };
+        let Some(entry) = self
+            .keys
+            .iter_mut()
+            .chain(self.slots.iter_mut().flatten())
+            .find(|k| shows(k, target) && (repeat || k.exit.is_none()))
+        else {
+            self.push_key(None, "MouseDouble", true, config);
+            return;
+        };
+
+        (entry.icon, entry.label) = double;
+        entry.time = Instant::now();
+        entry.exit = None;
+        if config.entry_animation == Animation::Scale {
+            entry.anim = 0.8;
+        }
+    }
+
+    /// Reorders the keys pressed within `window` of the newest one so modifiers come first,
+    /// as Ctrl, Shift, Alt, Meta, for `sort_simultaneous = "modifier_first"`.
+    ///
+    /// Only the unbroken run of such keys at the end of the row is sorted, and the sort is
+    /// stable, so other keys keep their arrival order.
+    pub fn order_modifiers_first(&mut self, window: Duration) {
+        let Some(newest) = self.keys.back().map(|k| k.time) else {
+            return;
+        };
+        let recent = self
+            .keys
+            .iter()
+            .rev()
+            .take_while(|k| {
+                !k.grouped && k.exit.is_none() && newest.saturating_duration_since(k.time) <= window
+            })
+            .count();
+
+        let start = self.keys.len() - recent;
+        self.keys.make_contiguous()[start..].sort_by_key(|k| {
+            let modifier = k.id.as_ref().and_then(|id| chord_modifier(&id.0));
+            match modifier {
+                Some("control") => 0,
+                Some("shift") => 1,
+                Some("alt") => 2,
+                Some("meta") => 3,
+                _ => 4,
+            }
+        });
+    }
+
+    /// Marks the entries showing the physical key `id` as no longer held.
+    pub fn release(&mut self, id: &KeyId) {
+        self.keys
+            .iter_mut()
+            .chain(self.slots.iter_mut().flatten())
+            .filter(|k| k.id.as_ref() == Some(id))
+            .for_each(|k| k.held = false);
+    }
+
+    /// Removes the last character of the newest `group_text` run, dropping the run once
+    /// it is empty.
+    ///
+    /// # Returns
+    /// `false` if no run is still being typed, so Backspace should show as a key.
+    fn pop_typed_char(&mut self, config: &Config) -> bool {
+        let window = Duration::from_millis(config.group_text_ms);
+        let Some(group) = self
+            .newest_mut()
+            .filter(|k| k.grouped && k.exit.is_none() && k.time.elapsed() <= window)
+        else {
+            return false;
+        };
+
+        group.label.pop();
+        group.time = Instant::now();
+        if group.label.is_empty() {
+            if self.slots.is_empty() {
+                self.keys.pop_back();
+            } else if let Some(slot) = self.slots.iter_mut().find(|slot| {
+                slot.as_ref()
+                    .is_some_and(|k| k.grouped && k.label.is_empty())
+            }) {
+                *slot = None;
+            }
+        }
+        true
+    }
+
+    /// Returns the most recently pushed entry, from the ring buffer or the slots.
+    fn newest_mut(&mut self) -> Option<&mut KeyEntry> {
+        if self.slots.is_empty() {
+            self.keys.back_mut()
+        } else {
+            self.slots.iter_mut().flatten().max_by_key(|k| k.time)
+        }
+    }
+
+    /// Places an entry in the first free slot, or replaces the oldest one if all are taken.
+    fn insert_into_slot(&mut self, entry: KeyEntry) {
+        let index = self.slots.iter().position(Option::is_none).or_else(|| {
+            self.slots
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, slot)| slot.as_ref().map(|k| k.time))
+                .map(|(i, _)| i)
+        });
+
+        if let Some(i) = index {
+            self.slots[i] = Some(entry);
+        }
+    }
+
+    /// Removes keys older than `timeout` (`timeout_ms`), blanking fixed slots in place.
+    ///
+    /// With an `exit` animation, expired keys are first marked as leaving and only
+    /// removed once that animation has finished.
+    pub fn expire(&mut self, now: Instant, timeout: Duration, exit: Animation) {
+        let keep = |k: &mut KeyEntry| {
+            if k.exit.is_none() && now.duration_since(k.time) >= timeout {
+                if exit == Animation::None {
+                    return false;
+                }
+                k.exit = Some(0.0);
+            }
+            k.exit.is_none_or(|progress| progress < 1.0)
+        };
+
+        self.keys.retain_mut(keep);
+        for slot in self.slots.iter_mut() {
+            if slot.as_mut().is_some_and(|k| !keep(k)) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Returns how many `ANIMATION_FRAME`s `elapsed`, the time since the last frame, is
+    /// worth, so animations run at the same speed at any frame rate.
+    ///
+    /// A slow frame counts as at most `max_step` (unless zero), so a hitch doesn't make
+    /// keys jump. The first step after a key arrives at an idle buffer counts as one frame.
+    pub fn animation_frames(&mut self, elapsed: Duration, max_step: Duration) -> f32 {
+        let elapsed = if std::mem::take(&mut self.restart_animation) {
+            ANIMATION_FRAME
+        } else if max_step.is_zero() {
+            elapsed
+        } else {
+            elapsed.min(max_step)
+        };
+        elapsed.as_secs_f32() / ANIMATION_FRAME.as_secs_f32()
+    }
+
+    /// Advances the entry animation of every buffered key, and the row slide, by `frames`.
+    pub fn advance_animations(&mut self, frames: f32) {
+        self.keys
+            .iter_mut()
+            .chain(self.slots.iter_mut().flatten())
+            .for_each(|k| k.advance_animation(frames));
+
+        self.scroll *= 0.7_f32.powf(frames);
+        if self.scroll < 0.5 {
+            self.scroll = 0.0;
+        }
+    }
+
+    /// Keeps a sample of every key category on screen, for `--preview`.
+    ///
+    /// The samples are added whenever the buffer is empty (at startup and after a clear),
+    /// and nothing expires while previewing, so styles can be tuned with hot-reload.
+    pub fn fill_preview(&mut self, config: &Config) {
+        self.set_slot_count(config.fixed_slots);
+        if self.is_empty() {
+            for (label, mouse) in PREVIEW_KEYS {
+                self.push_key(None, label, mouse, config);
+            }
+        }
+
+        let now = Instant::now();
+        self.keys
+            .iter_mut()
+            .chain(self.slots.iter_mut().flatten())
+            .for_each(|k| k.time = now);
+    }
+
+    /// Drops the oldest keys until at most `count` remain.
+    pub fn trim_to(&mut self, count: usize) {
+        while self.keys.len() > count {
+            self.keys.pop_front();
+        }
+    }
+}
+
+/// Sample inputs for `--preview`, one per `KeyCategory`, as `(raw label, is mouse)`.
+const PREVIEW_KEYS: [(&str, bool); 13] = [
+    ("Escape", false),
+    ("KeyA", false),
+    ("Num1", false),
+    ("ShiftLeft", false),
+    ("Backspace", false),
+    ("UpArrow", false),
+    ("PageUp", false),
+    ("Space", false),
+    ("Slash", false),
+    ("Unknown(255)", false),
+    ("F5", false),
+    ("VolumeUp", false),
+    ("MouseLeft", true),
+];
+
+/// Number of "clear all" requests made from outside the input stream (e.g. the tray).
+static CLEAR_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Asks every overlay to clear its keys on its next frame.
+// Only the Windows tray calls this
+#[cfg_attr(
+    not(all(feature = "windows-tray", target_os = "windows")),
+    allow(dead_code)
+)]
+pub fn request_clear() {
+    CLEAR_REQUESTS.fetch_add(1, Ordering::SeqCst);
+    wake();
+}
+
+/// Returns how many clears have been requested so far; overlays compare it to the
+/// count they last handled.
+pub fn clear_requests() -> usize {
+    CLEAR_REQUESTS.load(Ordering::SeqCst)
+}
+
+/// Turns a raw key or mouse label into the `(icon, label)` pair shown for it.
+///
+/// - Normalizes labels using platform-specific logic.
+/// - Applies `[labels]` overrides from the config, by raw or normalized name.
+/// - Parses label into icon + text if applicable (e.g., `"⇧ Shift"`).
+/// - Formats modifiers per `modifier_style`, and spells out glyphs when `glyphs` is off.
+pub fn format_label(label: &str, mouse: bool, config: &Config) -> (String, String) {
+    // Normalize the input label for consistency
+    let normalized = if !mouse {
+        normalize_key_label(label).to_string()
+    } else {
+        normalize_mouse_label(label)
+    };
+
+    // User overrides win over the built-in labels
+    let raw = config
+        .labels
+        .get(label)
+        .or_else(|| config.labels.get(&normalized))
+        .cloned()
+        .unwrap_or(normalized);
+
+    // Strip known key prefixes for better UI clarity
+    let label = match &raw {
+        l if l.starts_with("Key") => &l[3..],
+        l if l.starts_with("Num") && l.len() == 4 && l[3..].chars().all(|c| c.is_ascii_digit()) => {
+            &l[3..]
+        }
+        _ => &raw,
+    };
+
+    // Attempt to split icon and label by the first space
+    let (icon, label_text) = if let Some(space_idx) = label.find(' ') {
+        label.split_at(space_idx)
+    } else {
+        ("", label)
+    };
+
+    // Show modifiers as glyph, word, or both depending on `modifier_style`
+    let modifier_style = if config.glyphs {
+        config.modifier_style
+    } else {
+        ModifierStyle::Word
+    };
+    let (mut icon, mut label_clean) =
+        format_modifier_label(icon.trim(), label_text.trim(), modifier_style);
+
+    // Without glyphs, drop icons and spell out glyph-only labels
+    if !config.glyphs {
+        icon.clear();
+        if let Some(word) = glyph_word(&label_clean) {
+            label_clean = word.to_string();
+        }
+    }
+
+    // Format label text: e.g., F1, F12 stay uppercase, others retain formatting
+    let formatted_label = if label_clean.to_lowercase().starts_with("f") {
+        label_clean.to_uppercase()
+    } else {
+        label_clean.to_string()
+    };
+
+    (icon, formatted_label)
+}
+
+/// Returns the text a key adds to a `group_text` run: its character for letters, digits
+/// and symbols, or a space for the space bar. Other keys (modifiers, navigation, named
+/// keys such as Enter) return `None` and end the run.
+pub fn typed_text(icon: &str, label: &str) -> Option<String> {
+    match category_for_key(label) {
+        KeyCategory::Space => Some(" ".to_string()),
+        KeyCategory::Normal | KeyCategory::Numeric | KeyCategory::Symbol
+            if icon.is_empty() && label.chars().count() == 1 =>
+        {
+            Some(label.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Looks up the style for a buffered entry.
+///
+/// Typed text runs use the `Normal` style, widened so the whole run fits. With `auto_width`,
+/// `min_width` or `max_width`, the box is sized to the entry's measured `text_width` plus
+/// padding, within those bounds.
+pub fn entry_style(config: &Config, entry: &KeyEntry) -> Style {
+    if !entry.grouped {
+        let mut style = style_for(config, &entry.label);
+        if style.sized_to_label() {
+            // `min_width` defaults to the fixed `width`, unless sizing freely with `auto_width`
+            let min = match style.min_width {
+                Some(min) => min,
+                None if style.auto_width => 0.0,
+                None => style.width,
+            };
+            let max = style.max_width.unwrap_or(f32::INFINITY).max(min);
+            style.width = (entry.text_width + style.text_size).clamp(min, max);
+        }
+        return style;
+    }
+
+    let mut style = config
+        .styles
+        .get(&KeyCategory::Normal)
+        .cloned()
+        .unwrap_or_else(Config::fallback_style);
+    let text_width = entry.label.chars().count() as f32 * style.text_size * 0.6;
+    style.width = style.width.max(text_width + style.text_size);
+    style
+}
+
+/// Looks up the configured style for a key label, falling back to the default style.
+pub fn style_for(config: &Config, label: &str) -> Style {
+    config
+        .styles
+        .get(&category_for_key(label))
+        .cloned()
+        .unwrap_or_else(Config::fallback_style)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Builds a fully animated entry with the given label.
+    fn entry(label: &str) -> KeyEntry {
+        KeyEntry {
+            icon: String::new(),
+            label: label.to_string(),
+            anim: 1.0,
+            time: Instant::now(),
+            exit: None,
+            id: None,
+            grouped: false,
+            held: false,
+            pulse: 0.0,
+            text_width: 0.0,
+            velocity: 0.0,
+        }
+    }
+
+    /// Builds a buffer holding the given labels, oldest first.
+    pub(crate) fn buffer(labels: &[&str]) -> KeyBuffer {
+        let mut buffer = KeyBuffer::new();
+        buffer.keys.extend(labels.iter().map(|l| entry(l)));
+        buffer
+    }
+
+    #[test]
+    fn expire_drops_old_keys_and_blanks_slots() {
+        let mut buffer = buffer(&["A", "B"]);
+        buffer.keys[0].time = Instant::now() - Duration::from_secs(2);
+        buffer.set_slot_count(2);
+        buffer.slots[1] = Some(buffer.keys[0].clone());
+
+        buffer.expire(Instant::now(), Duration::from_secs(1), Animation::None);
+
+        assert_eq!(buffer.keys.len(), 1);
+        assert_eq!(buffer.keys[0].label, "B");
+        assert!(buffer.slots.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn keys_expire_after_the_timeout() {
+        let mut buffer = buffer(&["A"]);
+        buffer.keys[0].time = Instant::now() - Duration::from_millis(1500);
+
+        buffer.expire(Instant::now(), Duration::from_secs(2), Animation::None);
+        assert_eq!(buffer.keys.len(), 1);
+
+        buffer.expire(Instant::now(), Duration::from_secs(1), Animation::None);
+        assert!(buffer.keys.is_empty());
+    }
+
+    #[test]
+    fn exit_animation_keeps_expired_keys_until_it_finishes() {
+        let mut buffer = buffer(&["A"]);
+        buffer.keys[0].time = Instant::now() - Duration::from_secs(2);
+
+        buffer.expire(Instant::now(), Duration::from_secs(1), Animation::Fade);
+        assert_eq!(buffer.keys[0].exit, Some(0.0));
+
+        for _ in 0..10 {
+            buffer.advance_animations(1.0);
+            assert_eq!(buffer.keys.len(), 1);
+            buffer.expire(Instant::now(), Duration::from_secs(1), Animation::Fade);
+        }
+        assert!(buffer.keys.is_empty());
+    }
+
+    #[test]
+    fn animations_stop_at_fully_shown() {
+        let mut buffer = buffer(&["A"]);
+        buffer.keys[0].anim = 0.95;
+
+        buffer.advance_animations(1.0);
+        buffer.advance_animations(1.0);
+
+        assert!(buffer.keys[0].anim >= 1.0 && buffer.keys[0].anim < 1.1);
+    }
+
+    #[test]
+    fn trim_keeps_newest_keys() {
+        let mut buffer = buffer(&["A", "B", "C"]);
+        buffer.trim_to(2);
+
+        let labels: Vec<_> = buffer.keys.iter().map(|k| k.label.as_str()).collect();
+        assert_eq!(labels, ["B", "C"]);
+    }
+
+    #[test]
+    fn label_overrides_replace_built_in_text() {
+        let mut config = Config::default();
+        config.labels.insert("Space".into(), "⎵".into());
+        config.labels.insert("Return".into(), "↵ Return".into());
+
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key(None, "Space", false, &config);
+        buffer.push_key(None, "Return", false, &config);
+        buffer.push_key(None, "Tab", false, &config);
+
+        let shown: Vec<_> = buffer
+            .keys
+            .iter()
+            .map(|k| (k.icon.as_str(), k.label.as_str()))
+            .collect();
+        assert_eq!(shown, [("", "⎵"), ("↵", "Return"), ("", "Tab")]);
+    }
+
+    #[test]
+    fn modifier_style_controls_glyph_and_word() {
+        let shown = |style| {
+            let config = Config {
+                modifier_style: style,
+                ..Config::default()
+            };
+            let mut buffer = KeyBuffer::new();
+            buffer.push_key(None, "ShiftLeft", false, &config);
+            buffer.push_key(None, "Space", false, &config);
+            buffer
+                .keys
+                .iter()
+                .map(|k| (k.icon.clone(), k.label.clone()))
+                .collect::<Vec<_>>()
+        };
+        let pair = |icon: &str, label: &str| (icon.to_string(), label.to_string());
+
+        assert_eq!(
+            shown(ModifierStyle::Both),
+            [pair("⇧", "shift"), pair("󱁐", "space")]
+        );
+        assert_eq!(
+            shown(ModifierStyle::Glyph),
+            [pair("", "⇧"), pair("󱁐", "space")]
+        );
+        assert_eq!(
+            shown(ModifierStyle::Word),
+            [pair("", "Shift"), pair("󱁐", "space")]
+        );
+    }
+
+    #[test]
+    fn glyphs_off_swaps_icons_for_words() {
+        let config = Config {
+            glyphs: false,
+            modifier_style: ModifierStyle::Glyph,
+            ..Config::default()
+        };
+
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key(None, "ShiftLeft", false, &config);
+        buffer.push_key(None, "Space", false, &config);
+        buffer.push_key(None, "MouseUnknown(8)", true, &config);
+
+        let shown: Vec<_> = buffer
+            .keys
+            .iter()
+            .map(|k| (k.icon.as_str(), k.label.as_str()))
+            .collect();
+        let back = if cfg!(target_os = "windows") {
+            "btn8"
+        } else {
+            "mouse4"
+        };
+        assert_eq!(shown, [("", "Shift"), ("", "space"), ("", back)]);
+    }
+
+    #[test]
+    fn preview_shows_every_category_once() {
+        let config = Config::default();
+        let mut buffer = KeyBuffer::new();
+        buffer.fill_preview(&config);
+        buffer.fill_preview(&config);
+
+        let categories: Vec<_> = buffer
+            .keys
+            .iter()
+            .map(|k| category_for_key(&k.label))
+            .collect();
+        assert_eq!(categories.len(), PREVIEW_KEYS.len());
+        for category in &categories {
+            assert_eq!(categories.iter().filter(|c| *c == category).count(), 1);
+        }
+    }
+
+    #[test]
+    fn scroll_overflow_slides_new_keys_in_from_the_right() {
+        let config = Config {
+            overflow: Overflow::Scroll,
+            ..Config::default()
+        };
+
+        let mut buffer = buffer(&["A", "B", "C", "D"]);
+        buffer.push_key(None, "E", false, &config);
+        assert_eq!(buffer.scroll, 98.0);
+
+        // Keys scrolled out of view are kept, not trimmed
+        assert_eq!(buffer.keys.len(), 5);
+        for _ in 0..20 {
+            buffer.advance_animations(1.0);
+        }
+        assert_eq!(buffer.scroll, 0.0);
+    }
+
+    #[test]
+    fn same_key_with_another_label_refreshes_one_entry() {
+        let config = Config::default();
+        let id = KeyId("Num2".into());
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key(Some(&id), "Num2", false, &config);
+        buffer.push_key(Some(&id), "@", false, &config);
+
+        let labels: Vec<_> = buffer.keys.iter().map(|k| k.label.as_str()).collect();
+        assert_eq!(labels, ["@"]);
+    }
+
+    #[test]
+    fn group_text_joins_typed_keys_until_a_named_key() {
+        let config = Config {
+            group_text: true,
+            ..Config::default()
+        };
+        let mut buffer = KeyBuffer::new();
+        for (id, label) in [
+            ("KeyH", "h"),
+            ("KeyI", "i"),
+            ("KeyI", "i"),
+            ("Space", "Space"),
+            ("Num2", "2"),
+            ("ControlLeft", "ControlLeft"),
+            ("KeyC", "c"),
+        ] {
+            buffer.push_key(Some(&KeyId(id.into())), label, false, &config);
+        }
+
+        let labels: Vec<_> = buffer.keys.iter().map(|k| k.label.as_str()).collect();
+        assert_eq!(labels[0], "hii 2");
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels[2], "c");
+    }
+
+    #[test]
+    fn backspace_takes_back_typed_characters() {
+        let config = Config {
+            group_text: true,
+            ..Config::default()
+        };
+        let mut buffer = KeyBuffer::new();
+        for label in ["KeyO", "KeyK", "Backspace"] {
+            buffer.push_key(None, label, false, &config);
+        }
+        assert_eq!(buffer.keys.len(), 1);
+        assert_eq!(buffer.keys[0].label, "O");
+
+        // Emptying the run removes it; a further Backspace shows as a key
+        buffer.push_key(None, "Backspace", false, &config);
+        assert!(buffer.is_empty());
+        buffer.push_key(None, "Backspace", false, &config);
+        assert_eq!(buffer.keys[0].label, "back");
+    }
+
+    #[test]
+    fn released_keys_stop_pulsing() {
+        let config = Config::default();
+        let ctrl = KeyId("ControlLeft".into());
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key(Some(&ctrl), "ControlLeft", false, &config);
+        buffer.advance_animations(1.0);
+        assert!(buffer.keys[0].held);
+        assert_eq!(buffer.keys[0].pulse, 1.0);
+
+        buffer.release(&ctrl);
+        for _ in 0..40 {
+            buffer.advance_animations(1.0);
+        }
+        assert!(!buffer.keys[0].held);
+        assert_eq!(buffer.keys[0].pulse, 0.0);
+    }
+
+    #[test]
+    fn sticky_keys_stop_animating_once_shown() {
+        let mut config = Config {
+            sticky: true,
+            ..Config::default()
+        };
+        let mut buffer = KeyBuffer::new();
+        assert!(!buffer.is_animating(&config));
+
+        buffer.push_key(None, "A", false, &config);
+        assert!(buffer.is_animating(&config));
+        for _ in 0..10 {
+            buffer.advance_animations(1.0);
+        }
+        assert!(!buffer.is_animating(&config));
+
+        // Without `sticky`, shown keys still have an expiry to reach
+        config.sticky = false;
+        assert!(buffer.is_animating(&config));
+    }
+
+    #[test]
+    fn min_and_max_width_fit_the_measured_label() {
+        let mut config = Config::default();
+        let normal = config.styles.get_mut(&KeyCategory::Normal).unwrap();
+        normal.min_width = Some(60.0);
+        normal.max_width = Some(150.0);
+
+        // Normal text is 20 high, which is also the padding around the label
+        let mut key = entry("A");
+        key.text_width = 12.0;
+        assert_eq!(entry_style(&config, &key).width, 60.0);
+        key.text_width = 100.0;
+        assert_eq!(entry_style(&config, &key).width, 120.0);
+        key.text_width = 400.0;
+        assert_eq!(entry_style(&config, &key).width, 150.0);
+    }
+
+    #[test]
+    fn auto_width_sizes_boxes_to_their_label() {
+        let mut config = Config::default();
+        config
+            .styles
+            .get_mut(&KeyCategory::Normal)
+            .unwrap()
+            .auto_width = true;
+
+        let mut key = entry("A");
+        key.text_width = 12.0;
+        assert_eq!(entry_style(&config, &key).width, 32.0);
+    }
+
+    #[test]
+    fn quick_presses_have_high_velocity() {
+        assert_eq!(press_velocity(Duration::from_millis(30)), 1.0);
+        assert_eq!(press_velocity(Duration::from_secs(2)), 0.0);
+        let medium = press_velocity(Duration::from_millis(250));
+        assert!(medium > 0.0 && medium < 1.0);
+
+        // A refreshed entry takes on the velocity of its latest press
+        let config = Config::default();
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key_with_velocity(None, "A", false, 0.2, &config);
+        buffer.push_key_with_velocity(None, "A", false, 0.9, &config);
+        assert_eq!(buffer.keys.len(), 1);
+        assert_eq!(buffer.keys[0].velocity, 0.9);
+    }
+
+    #[test]
+    fn simultaneous_modifiers_move_before_the_key() {
+        let config = Config::default();
+        let mut buffer = KeyBuffer::new();
+        for id in ["KeyA", "KeyP", "ShiftLeft", "ControlLeft"] {
+            buffer.push_key(Some(&KeyId(id.into())), id, false, &config);
+        }
+        // KeyA came 30 ms before the chord, the rest within the same instant
+        let now = Instant::now();
+        for key in &mut buffer.keys {
+            key.time = now;
+        }
+        buffer.keys[0].time = now - Duration::from_millis(30);
+        buffer.order_modifiers_first(Duration::from_millis(20));
+
+        let ids: Vec<_> = buffer
+            .keys
+            .iter()
+            .map(|k| k.id.clone().unwrap().0)
+            .collect();
+        assert_eq!(ids, ["KeyA", "ControlLeft", "ShiftLeft", "KeyP"]);
+    }
+
+    #[test]
+    fn blank_labels_are_skipped_or_replaced() {
+        let mut config = Config::default();
+        config.labels.insert("KeyQ".into(), " ".into());
+
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key(None, "KeyQ", false, &config);
+        assert_eq!(buffer.keys[0].label, EMPTY_PLACEHOLDER);
+
+        config.empty_label = EmptyLabel::Skip;
+        let mut buffer = KeyBuffer::new();
+        buffer.push_key(None, "KeyQ", false, &config);
+        assert!(buffer.keys.is_empty());
+    }
+
+    #[test]
+    fn animation_frames_follow_elapsed_time_up_to_the_cap() {
+        let config = Config::default();
+        let mut buffer = KeyBuffer::new();
+        let cap = Duration::from_millis(50);
+
+        // A key arriving at an idle overlay starts from one frame, however long it sat still
+        buffer.push_key(None, "A", false, &config);
+        assert_eq!(buffer.animation_frames(Duration::from_secs(5), cap), 1.0);
+
+        // Then frames scale with the time between them
+        let frames = buffer.animation_frames(ANIMATION_FRAME * 2, cap);
+        assert!((frames - 2.0).abs() < 1e-3);
+
+        // A one-second hitch advances by no more than the cap
+        let frames = buffer.animation_frames(Duration::from_secs(1), cap);
+        assert!((frames - 3.0).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::input::input::KeyId;
+use crate::input::keymap::chord_modifier;
+
+/// Holds back lone modifier presses until a non-modifier key joins them in a chord.
+///
+/// Used by `modifiers_only_in_chords`: tapping Ctrl shows nothing, while Ctrl+S shows
+/// both keys. Modifiers are tracked by name and key id until their release event arrives.
+pub struct ChordFilter {
+    held: Vec<(&'static str, KeyId, String)>, // Held modifiers as (name, key, press label)
+}
+
+impl ChordFilter {
+    pub fn new() -> Self {
+        Self { held: Vec::new() }
+    }
+
+    /// Records a key press and returns the keys and labels to show for it, in display order.
+    ///
+    /// A modifier on its own yields nothing; any other key yields the held modifiers
+    /// followed by the key itself.
+    pub fn press(&mut self, id: &KeyId, label: &str) -> Vec<(KeyId, String)> {
+        if let Some(name) = modifier_name(id, label) {
+            if !self.held.iter().any(|(held, _, _)| *held == name) {
+                self.held.push((name, id.clone(), label.to_string()));
+            }
+            return Vec::new();
+        }
+
+        self.held
+            .iter()
+            .map(|(_, id, label)| (id.clone(), label.clone()))
+            .chain(std::iter::once((id.clone(), label.to_string())))
+            .collect()
+    }
+
+    /// Returns the names of the modifiers currently held.
+    pub fn held(&self) -> Vec<&'static str> {
+        self.held.iter().map(|(name, _, _)| *name).collect()
+    }
+
+    /// Records a key release, ending any chord the released modifier was part of.
+    ///
+    /// The key id matches even if the release label differs from the press label.
+    pub fn release(&mut self, id: &KeyId, label: &str) {
+        let name = modifier_name(id, label);
+        self.held
+            .retain(|(held, held_id, _)| held_id != id && Some(*held) != name);
+    }
+}
+
+/// Returns the chord modifier a key is, judged by its label or else its key name.
+fn modifier_name(id: &KeyId, label: &str) -> Option<&'static str> {
+    chord_modifier(label).or_else(|| chord_modifier(&id.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a key id from an rdev key name.
+    fn key(name: &str) -> KeyId {
+        KeyId(name.to_string())
+    }
+
+    /// Presses a key and returns just the labels to show.
+    fn press(chords: &mut ChordFilter, id: &str, label: &str) -> Vec<String> {
+        chords
+            .press(&key(id), label)
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect()
+    }
+
+    #[test]
+    fn lone_modifier_tap_shows_nothing() {
+        let mut chords = ChordFilter::new();
+
+        assert!(press(&mut chords, "ControlLeft", "⌃ control").is_empty());
+        chords.release(&key("ControlLeft"), "⌃ control");
+        assert_eq!(press(&mut chords, "KeyS", "S"), ["S"]);
+    }
+
+    #[test]
+    fn held_modifiers_join_the_next_key() {
+        let mut chords = ChordFilter::new();
+
+        assert!(press(&mut chords, "ControlLeft", "⌃ control").is_empty());
+        assert!(press(&mut chords, "ShiftLeft", "⇧ shift").is_empty());
+        // Auto-repeat of a held modifier doesn't duplicate it
+        assert!(press(&mut chords, "ControlLeft", "⌃ control").is_empty());
+        assert_eq!(
+            chords.press(&key("KeyS"), "S"),
+            [
+                (key("ControlLeft"), "⌃ control".to_string()),
+                (key("ShiftLeft"), "⇧ shift".to_string()),
+                (key("KeyS"), "S".to_string()),
+            ]
+        );
+
+        chords.release(&key("ShiftLeft"), "⇧ shift");
+        assert_eq!(press(&mut chords, "KeyA", "A"), ["⌃ control", "A"]);
+    }
+
+    #[test]
+    fn release_matches_raw_key_names() {
+        let mut chords = ChordFilter::new();
+
+        press(&mut chords, "ShiftLeft", "⇧ shift");
+        chords.release(&key("ShiftRight"), "ShiftRight");
+        assert_eq!(press(&mut chords, "KeyA", "A"), ["A"]);
+    }
+
+    #[test]
+    fn release_matches_key_id_when_labels_differ() {
+        let mut chords = ChordFilter::new();
+
+        press(&mut chords, "ControlLeft", "⌃ control");
+        chords.release(&key("ControlLeft"), "");
+        assert_eq!(press(&mut chords, "KeyA", "A"), ["A"]);
+    }
+}
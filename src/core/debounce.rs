@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Filters repeat events per key and turns quick double left clicks into `MouseDouble`.
+pub struct Debouncer {
+    last_seen: HashMap<String, Instant>, // When each label was last shown
+    last_click: Option<(String, Instant)>, // Last mouse button and time, for double-click detection
+}
+
+impl Debouncer {
+    pub fn new() -> Self {
+        Self {
+            last_seen: HashMap::new(),
+            last_click: None,
+        }
+    }
+
+    /// Returns `true` if `label` should be shown, i.e. it wasn't shown within its own
+    /// `window`. Each key is timed separately, so holding one key never hides another.
+    pub fn accept(&mut self, label: &str, now: Instant, window: Duration) -> bool {
+        let repeated = self
+            .last_seen
+            .get(label)
+            .is_some_and(|at| now.duration_since(*at) < window);
+
+        if !repeated {
+            self.last_seen.insert(label.to_string(), now);
+        }
+        !repeated
+    }
+
+    /// Forgets `label` so that pressing it again is shown straight away.
+    ///
+    /// Used on key release with `group_text`, where fast double letters ("ll") are real
+    /// presses rather than auto-repeat and must not be filtered. There, keys are debounced
+    /// by their `KeyId` name, since a press and its release can carry different labels.
+    pub fn release(&mut self, label: &str) {
+        self.last_seen.remove(label);
+    }
+
+    /// Forgets keys whose window has passed, keeping the map small.
+    pub fn prune(&mut self, now: Instant, window: Duration) {
+        self.last_seen
+            .retain(|_, at| now.duration_since(*at) < window);
+    }
+
+    /// Turns a second left click on the same button within `threshold` into a
+    /// `MouseDouble` label. Other clicks are passed through unchanged.
+    pub fn detect_double_click(
+        &mut self,
+        label: String,
+        now: Instant,
+        threshold: Duration,
+    ) -> String {
+        let is_double = label == "MouseLeft"
+            && self
+                .last_click
+                .as_ref()
+                .is_some_and(|(prev, at)| *prev == label && now.duration_since(*at) <= threshold);
+
+        if is_double {
+            // Reset so a triple click doesn't register as two doubles
+            self.last_click = None;
+            "MouseDouble".to_string()
+        } else {
+            self.last_click = Some((label.clone(), now));
+            label
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_are_suppressed_per_key() {
+        let mut debouncer = Debouncer::new();
+        let start = Instant::now();
+        let window = Duration::from_millis(250);
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert!(debouncer.accept("A", at(0), window));
+        assert!(!debouncer.accept("A", at(100), window));
+
+        // Another key has its own window
+        assert!(debouncer.accept("B", at(200), window));
+        assert!(debouncer.accept("A", at(260), window));
+        assert!(!debouncer.accept("B", at(300), window));
+        assert!(debouncer.accept("B", at(450), window));
+    }
+
+    #[test]
+    fn double_left_click_within_threshold() {
+        let mut debouncer = Debouncer::new();
+        let start = Instant::now();
+        let threshold = Duration::from_millis(400);
+
+        let first = debouncer.detect_double_click("MouseLeft".into(), start, threshold);
+        let second = debouncer.detect_double_click(
+            "MouseLeft".into(),
+            start + Duration::from_millis(200),
+            threshold,
+        );
+        let third = debouncer.detect_double_click(
+            "MouseLeft".into(),
+            start + Duration::from_millis(300),
+            threshold,
+        );
+
+        assert_eq!(first, "MouseLeft");
+        assert_eq!(second, "MouseDouble");
+        assert_eq!(third, "MouseLeft");
+    }
+
+    #[test]
+    fn slow_or_other_button_clicks_are_not_doubles() {
+        let mut debouncer = Debouncer::new();
+        let start = Instant::now();
+        let threshold = Duration::from_millis(400);
+
+        debouncer.detect_double_click("MouseLeft".into(), start, threshold);
+        let slow = debouncer.detect_double_click(
+            "MouseLeft".into(),
+            start + Duration::from_millis(500),
+            threshold,
+        );
+        assert_eq!(slow, "MouseLeft");
+
+        debouncer.detect_double_click("MouseRight".into(), start, threshold);
+        let right = debouncer.detect_double_click(
+            "MouseRight".into(),
+            start + Duration::from_millis(100),
+            threshold,
+        );
+        assert_eq!(right, "MouseRight");
+    }
+}
@@ -0,0 +1,6 @@
+//! Rendering-independent state: what is on screen and why, without any painting.
+
+pub mod buffer;
+pub mod chord;
+pub mod debounce;
+pub mod wake;
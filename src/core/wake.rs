@@ -0,0 +1,62 @@
+//! Waking the overlay when something changes outside the input stream, such as a tray
+//! request or a failed listener. Between inputs the UI sleeps (or only polls slowly), so
+//! without a wake these would wait for the next key press.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Repaints every overlay; set once the UI is running.
+static WAKER: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+/// When the overlay last drew a frame, and the shortest time `max_fps` allows between frames.
+static LAST_FRAME: Mutex<Option<(Instant, Duration)>> = Mutex::new(None);
+
+/// Sets how `wake` repaints the overlay. Only the first call has any effect.
+pub fn set_waker(waker: impl Fn() + Send + Sync + 'static) {
+    let _ = WAKER.set(Box::new(waker));
+}
+
+/// Asks the overlay to draw a frame soon, from any thread. Does nothing before the UI runs,
+/// since its first frame picks up the change anyway.
+pub fn wake() {
+    if let Some(waker) = WAKER.get() {
+        waker();
+    }
+}
+
+/// Records that the overlay drew a frame, with the frame time `max_fps` allows (zero when
+/// uncapped), so wakes between frames can be held back by `frame_delay`.
+pub fn frame_drawn(min_frame: Duration) {
+    if let Ok(mut last) = LAST_FRAME.lock() {
+        *last = Some((Instant::now(), min_frame));
+    }
+}
+
+/// Returns how long a repaint should wait to stay within `max_fps`: what is left of the
+/// frame time since the last frame, or zero if it has passed (or there is no cap).
+///
+/// Wakers repaint after this delay, so a burst of events shares one frame.
+pub fn frame_delay() -> Duration {
+    LAST_FRAME
+        .lock()
+        .ok()
+        .and_then(|last| *last)
+        .map_or(Duration::ZERO, |(at, min_frame)| {
+            min_frame.saturating_sub(at.elapsed())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wakes_wait_for_the_rest_of_the_frame_budget() {
+        frame_drawn(Duration::from_secs(10));
+        let delay = frame_delay();
+        assert!(delay > Duration::from_secs(9) && delay <= Duration::from_secs(10));
+
+        frame_drawn(Duration::ZERO);
+        assert_eq!(frame_delay(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,98 @@
+use std::{fmt, io, path::PathBuf};
+
+/// Everything that can go wrong in FerrisKeys, with enough context to tell the user what
+/// to fix.
+#[derive(Debug)]
+pub enum FerrisKeysError {
+    /// Reading or writing a file (usually the config) failed.
+    Io { path: PathBuf, source: io::Error },
+    /// The config file isn't valid TOML.
+    ConfigParse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// The settings were saved, but come from `Config::in_memory` rather than a file.
+    NoConfigFile,
+    /// The settings couldn't be written out as TOML (`--dump-config`).
+    ConfigSerialize(toml::ser::Error),
+    /// The active keyboard layout couldn't be detected; set `layout` in the config instead.
+    LayoutDetection(String),
+    /// Watching the config file for changes failed; hot-reload falls back to polling.
+    #[cfg(feature = "hot-reload")]
+    Watcher(notify::Error),
+    /// The session is a Wayland setup where creating the overlay window is known to fail
+    /// (e.g. on a Raspberry Pi).
+    UnsupportedWayland,
+    /// The overlay window couldn't be created or stopped with an error.
+    Ui(eframe::Error),
+}
+
+impl FerrisKeysError {
+    /// Wraps an I/O error on `path`.
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for FerrisKeysError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                write!(f, "Failed to access '{}': {}", path.display(), source)
+            }
+            Self::ConfigParse { path, source } => {
+                write!(f, "Invalid config '{}': {}", path.display(), source)
+            }
+            Self::NoConfigFile => write!(f, "The settings have no config file to be saved to"),
+            Self::ConfigSerialize(e) => write!(f, "Failed to write the config as TOML: {e}"),
+            Self::LayoutDetection(reason) => {
+                write!(f, "Failed to detect the keyboard layout: {reason}")
+            }
+            #[cfg(feature = "hot-reload")]
+            Self::Watcher(e) => write!(f, "Failed to watch config file: {e}"),
+            Self::UnsupportedWayland => write!(
+                f,
+                "Wayland detected and native window creation may be unsupported on this \
+                 system. Try launching with `LIBGL_ALWAYS_SOFTWARE=1 ./ferriskeys`, or use an \
+                 X11 session instead."
+            ),
+            Self::Ui(e) => write!(f, "Failed to run the overlay: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for FerrisKeysError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::ConfigParse { source, .. } => Some(source),
+            Self::ConfigSerialize(e) => Some(e),
+            Self::NoConfigFile | Self::LayoutDetection(_) | Self::UnsupportedWayland => None,
+            #[cfg(feature = "hot-reload")]
+            Self::Watcher(e) => Some(e),
+            Self::Ui(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "hot-reload")]
+impl From<notify::Error> for FerrisKeysError {
+    fn from(e: notify::Error) -> Self {
+        Self::Watcher(e)
+    }
+}
+
+impl From<toml::ser::Error> for FerrisKeysError {
+    fn from(e: toml::ser::Error) -> Self {
+        Self::ConfigSerialize(e)
+    }
+}
+
+impl From<eframe::Error> for FerrisKeysError {
+    fn from(e: eframe::Error) -> Self {
+        Self::Ui(e)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::input::input::InputEvent;
+use std::{
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// Fans a single stream of `InputEvent`s out to any number of subscribers.
+///
+/// The platform listener writes into one `mpsc` channel, which only supports a single
+/// consumer. The broadcaster owns that receiver on a background thread and clones every
+/// event into each subscriber's own channel, so the overlay, mirrors, loggers, etc. can
+/// all consume the same stream independently.
+#[derive(Clone)]
+pub struct Broadcaster {
+    subscribers: Arc<Mutex<Vec<Sender<InputEvent>>>>,
+    wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+/// A callback run on the broadcaster thread after every event is delivered.
+type Waker = Box<dyn Fn() + Send>;
+
+impl Broadcaster {
+    /// Starts forwarding events from `rx` to all current and future subscribers.
+    ///
+    /// Subscribers whose receiver has been dropped are pruned on the next event.
+    pub fn spawn(rx: Receiver<InputEvent>) -> Self {
+        let subscribers: Arc<Mutex<Vec<Sender<InputEvent>>>> = Arc::new(Mutex::new(Vec::new()));
+        let wakers: Arc<Mutex<Vec<Waker>>> = Arc::new(Mutex::new(Vec::new()));
+        let targets = subscribers.clone();
+        let signals = wakers.clone();
+
+        thread::spawn(move || {
+            for event in rx.iter() {
+                targets
+                    .lock()
+                    .unwrap()
+                    .retain(|tx| tx.send(event.clone()).is_ok());
+
+                // Wake consumers (e.g. the UI) now that the event is in their channel
+                for wake in signals.lock().unwrap().iter() {
+                    wake();
+                }
+            }
+        });
+
+        Self {
+            subscribers,
+            wakers,
+        }
+    }
+
+    /// Registers a callback to run after each event has been delivered to subscribers.
+    ///
+    /// Used to request an egui repaint, so the UI reacts to input immediately instead of
+    /// polling for it.
+    pub fn on_event(&self, wake: impl Fn() + Send + 'static) {
+        self.wakers.lock().unwrap().push(Box::new(wake));
+    }
+
+    /// Registers a new subscriber and returns the receiving end of its channel.
+    ///
+    /// Only events sent after subscribing are delivered.
+    pub fn subscribe(&self) -> Receiver<InputEvent> {
+        let (tx, rx) = channel();
+        self.subscribers.lock().unwrap().push(tx);
+        rx
+    }
+}
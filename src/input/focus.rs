@@ -0,0 +1,96 @@
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::Duration;
+
+/// The window that currently has keyboard focus, as reported by the platform.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FocusedWindow {
+    /// Window class or application id (e.g. `"code"`, `"Alacritty"`).
+    pub class: String,
+    /// Window title (e.g. `"main.rs - FerrisKeys - Visual Studio Code"`).
+    pub title: String,
+}
+
+impl FocusedWindow {
+    /// Returns `true` if any pattern appears in the class or title, ignoring case.
+    pub fn matches(&self, patterns: &[String]) -> bool {
+        let class = self.class.to_lowercase();
+        let title = self.title.to_lowercase();
+        patterns.iter().any(|pattern| {
+            let pattern = pattern.to_lowercase();
+            class.contains(&pattern) || title.contains(&pattern)
+        })
+    }
+}
+
+// Platform-specific focus detection
+#[cfg(target_os = "windows")]
+pub use crate::platform::windows::focus::focused_window;
+
+#[cfg(target_os = "linux")]
+pub use crate::platform::linux::focus::focused_window;
+
+/// Focus detection is not implemented on this platform yet.
+#[cfg(target_os = "macos")]
+pub fn focused_window() -> Option<FocusedWindow> {
+    None
+}
+
+/// How often `watch_focus` asks the platform for the focused window.
+const FOCUS_POLL: Duration = Duration::from_millis(250);
+
+/// The focused window last seen by the `watch_focus` thread.
+static FOCUSED: Mutex<Option<FocusedWindow>> = Mutex::new(None);
+
+/// Starts the focus watcher once; later calls do nothing.
+static WATCHING: Once = Once::new();
+
+/// Starts a background thread that checks the focused window every `FOCUS_POLL`, calling
+/// `wake` whenever it changes so the overlay can redraw.
+///
+/// Asking the platform can mean spawning a process (`xprop` on X11), so it is kept off the
+/// UI thread; read the result with `last_focused_window`.
+pub fn watch_focus(wake: impl Fn() + Send + 'static) {
+    WATCHING.call_once(|| {
+        thread::spawn(move || loop {
+            let window = focused_window();
+            let changed = match FOCUSED.lock() {
+                Ok(mut focused) if *focused != window => {
+                    *focused = window;
+                    true
+                }
+                _ => false,
+            };
+            if changed {
+                wake();
+            }
+            thread::sleep(FOCUS_POLL);
+        });
+    });
+}
+
+/// Returns the focused window last seen by `watch_focus`, or `None` if it can't be
+/// detected (or the watcher hasn't checked yet).
+pub fn last_focused_window() -> Option<FocusedWindow> {
+    FOCUSED.lock().ok().and_then(|focused| focused.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(class: &str, title: &str) -> FocusedWindow {
+        FocusedWindow {
+            class: class.into(),
+            title: title.into(),
+        }
+    }
+
+    #[test]
+    fn matches_class_or_title_ignoring_case() {
+        let patterns = vec!["code".to_string(), "vim".to_string()];
+        assert!(window("Code", "main.rs").matches(&patterns));
+        assert!(window("Alacritty", "NVIM - notes.md").matches(&patterns));
+        assert!(!window("firefox", "Mozilla Firefox").matches(&patterns));
+    }
+}
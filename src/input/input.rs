@@ -1,9 +1,96 @@
+use crate::core::wake::wake;
+use rdev::Key;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Identifies a physical key independently of the label shown for it, which can change
+/// with Shift, AltGr or the keyboard layout (e.g. `Num2` for "2" and "@").
+///
+/// It is the rdev key name rather than `rdev::Key` itself so it can be written to and
+/// read back from recordings unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyId(pub String);
+
+impl KeyId {
+    /// Returns the identity of an rdev key, e.g. `KeyA` or `ShiftLeft`.
+    pub fn of(key: Key) -> Self {
+        Self(format!("{key:?}"))
+    }
+}
+
+/// An input event from a platform listener (or a replayed recording).
+///
+/// Key events carry both the key's stable identity and its display label; match on the
+/// `KeyId` to recognise a key, and only show the label.
 #[derive(Debug, Clone)]
 pub enum InputEvent {
-    KeyPress(String),
+    KeyPress(KeyId, String),   // The key pressed, and the label to display for it
+    KeyRelease(KeyId, String), // The key released, and its physical label
     MouseClick(String),
 }
 
+/// Why the input listener failed to start, if it did.
+static LISTENER_FAILURE: OnceLock<String> = OnceLock::new();
+
+/// Records that the input listener failed to start, so the overlay can explain it
+/// instead of staying empty. `message` should say how to fix it; it is also logged.
+pub fn report_listener_failure(message: String) {
+    error!("{message}");
+    let _ = LISTENER_FAILURE.set(message);
+    wake();
+}
+
+/// Returns the message passed to `report_listener_failure`, if the listener failed.
+pub fn listener_failure() -> Option<&'static str> {
+    LISTENER_FAILURE.get().map(String::as_str)
+}
+
+/// Where the mouse cursor was last seen by the input listener, in desktop pixels.
+static CURSOR: Mutex<Option<[f32; 2]>> = Mutex::new(None);
+
+/// Whether cursor moves wake the UI, set while `follow_cursor` is on.
+static WAKE_ON_CURSOR: AtomicBool = AtomicBool::new(false);
+
+/// When a cursor move last woke the UI.
+static LAST_CURSOR_WAKE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Shortest time between overlay moves with `follow_cursor`, so it doesn't jitter.
+/// Cursor moves wake the UI at most this often.
+pub const FOLLOW_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Records the cursor position from a mouse-move event, for `follow_cursor`.
+///
+/// Moves are stored rather than sent as events, since they arrive far faster than
+/// frames are drawn and only the latest position matters. While `set_wake_on_cursor` is
+/// on, they also wake the UI, at most once per `FOLLOW_INTERVAL`.
+pub fn record_cursor(x: f64, y: f64) {
+    if let Ok(mut cursor) = CURSOR.lock() {
+        *cursor = Some([x as f32, y as f32]);
+    }
+
+    if !WAKE_ON_CURSOR.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(mut last) = LAST_CURSOR_WAKE.lock() {
+        if last.is_some_and(|time| time.elapsed() < FOLLOW_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+    }
+    wake();
+}
+
+/// Makes cursor moves wake the UI, so `follow_cursor` keeps up while nothing else is drawn.
+pub fn set_wake_on_cursor(enabled: bool) {
+    WAKE_ON_CURSOR.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the last cursor position passed to `record_cursor`, if the mouse has moved.
+pub fn cursor_position() -> Option<[f32; 2]> {
+    CURSOR.lock().ok().and_then(|cursor| *cursor)
+}
+
 // Delegate to platform-specific input backend
 #[cfg(target_os = "windows")]
 pub use crate::platform::windows::input::start_input_listener;
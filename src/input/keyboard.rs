@@ -149,3 +149,34 @@ pub fn resolve_physical_key(key: Key) -> String {
         k => format!("{:?}", k),
     }
 }
+
+/// Turns the text a key typed under the active layout into its label. Letters are shown
+/// as keycaps (uppercase), like the physical map.
+///
+/// Returns `None` for blank or non-printable text, e.g. the control characters Ctrl+C and
+/// Ctrl+V type, so callers fall back to the physical key and shortcuts read as `C` or `V`.
+pub fn typed_label(text: &str) -> Option<String> {
+    if text.trim().is_empty() || text.chars().any(char::is_control) {
+        return None;
+    }
+
+    Some(if text.chars().all(char::is_alphabetic) {
+        text.to_uppercase()
+    } else {
+        text.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_label_rejects_control_characters_from_shortcuts() {
+        assert_eq!(typed_label("a").as_deref(), Some("A"));
+        assert_eq!(typed_label("€").as_deref(), Some("€"));
+        assert_eq!(typed_label("\u{3}"), None); // Ctrl+C
+        assert_eq!(typed_label("\u{16}"), None); // Ctrl+V
+        assert_eq!(typed_label(" "), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::config::ModifierStyle;
 use crate::input::layout::KeyboardLayout;
 use rdev::Key;
 
@@ -30,10 +31,18 @@ pub enum KeyCategory {
 /// * `KeyCategory` representing how the key should be classified.
 pub fn category_for_key(key: &str) -> KeyCategory {
     match key.to_ascii_lowercase().as_str() {
-        "󰍽" | "left" | "right" | "middle" => KeyCategory::Mouse,
-        "meta" | "esc" | "escape" | "\u{f0206} esc" => KeyCategory::Escape,
-        "ctrl" | "control" | "⌃ control" | "shift" | "⇧ shift" | "alt" | "⌥ alt" | "tab"
-        | "num" | "numlock" | "caps" => KeyCategory::Modifier,
+        "󰍽" | "left" | "right" | "middle" | "double" | "drag" | "󰁍" | "󰁔" | "mouse4" | "mouse5" => {
+            KeyCategory::Mouse
+        }
+
+        // Extra mouse buttons without a dedicated glyph (e.g. "btn12")
+        k if k.len() > 3 && k.starts_with("btn") && k[3..].chars().all(|c| c.is_ascii_digit()) => {
+            KeyCategory::Mouse
+        }
+
+        "meta" | "\u{e62a}" | "esc" | "escape" | "\u{f0206} esc" => KeyCategory::Escape,
+        "ctrl" | "control" | "⌃ control" | "⌃" | "shift" | "⇧ shift" | "⇧" | "alt" | "⌥ alt"
+        | "⌥" | "tab" | "num" | "numlock" | "caps" | "⇪" => KeyCategory::Modifier,
 
         "󰹑" | "ps" | "backspace" | "delete" | "del" | "back" | "ins" | "insert" => {
             KeyCategory::Editor
@@ -50,7 +59,13 @@ pub fn category_for_key(key: &str) -> KeyCategory {
         // Common symbols used in programming and input
         "{" | "}" | "<" | ">" | "|" | "£" | "$" | "%" | "^" | "&" | "_" | "¬" | "#" | "`" | "("
         | ")" | "@" | "+" | "-" | "=" | "*" | "\\" | "/" | "," | "." | ";" | ":" | "!" | "'"
-        | "[" | "]" | "?" | "~" | "\"" => KeyCategory::Symbol,
+        | "[" | "]" | "?" | "~" | "\"" | "¡" | "¿" | "·" | "º" | "ª" | "´" | "¨" | "°" | "§"
+        | "¤" | "½" => KeyCategory::Symbol,
+
+        // Currency, math and typographic symbols, mostly typed with AltGr on European layouts
+        "€" | "¥" | "¢" | "₹" | "₽" | "₩" | "₺" | "±" | "×" | "÷" | "¹" | "²" | "³" | "¼" | "¾"
+        | "‰" | "≠" | "≤" | "≥" | "¦" | "«" | "»" | "‹" | "›" | "„" | "“" | "”" | "‘" | "’"
+        | "–" | "—" | "…" | "¶" | "©" | "®" | "™" | "†" | "•" => KeyCategory::Symbol,
 
         // Function keys like F1–F24
         k if k.starts_with('f')
@@ -80,8 +95,8 @@ pub fn category_for_key(key: &str) -> KeyCategory {
         // Digits only
         k if k.chars().all(|c| c.is_ascii_digit()) => KeyCategory::Numeric,
 
-        // Alphabetic only
-        k if k.chars().all(|c| c.is_ascii_alphabetic()) => KeyCategory::Normal,
+        // Alphabetic only, including accented letters such as ñ or è
+        k if k.chars().all(char::is_alphabetic) => KeyCategory::Normal,
 
         _ => KeyCategory::Unknown,
     }
@@ -89,20 +104,101 @@ pub fn category_for_key(key: &str) -> KeyCategory {
 
 /// Converts raw mouse button identifiers into standardized labels with icons.
 ///
+/// Extra buttons arrive from rdev as `Button::Unknown(n)`. The common back/forward
+/// side buttons are mapped to arrow glyphs (X11 reports them as 8/9, Windows as 1/2);
+/// any other code is shown as `btnN` so it still lands in the `Mouse` category.
+///
 /// # Arguments
 /// * `raw` - A `&str` representing the raw rdev mouse button label.
 ///
 /// # Returns
 /// * A standardized label with an icon, or the original string if unrecognized.
-pub fn normalize_mouse_label(raw: &str) -> &str {
+pub fn normalize_mouse_label(raw: &str) -> String {
     match raw {
-        "MouseLeft" => "󰍽 left",
-        "MouseRight" => "󰍽 right ",
-        "MouseMiddle" => "󰍽 middle",
-        _ => raw,
+        "MouseLeft" => "󰍽 left".to_string(),
+        "MouseRight" => "󰍽 right ".to_string(),
+        "MouseMiddle" => "󰍽 middle".to_string(),
+        "MouseDouble" => "󰍽 double".to_string(),
+        _ => match raw
+            .strip_prefix("MouseUnknown(")
+            .and_then(|r| r.strip_suffix(')'))
+            .and_then(|n| n.parse::<u8>().ok())
+        {
+            Some(code) if code == MOUSE_BACK => "󰍽 󰁍".to_string(),
+            Some(code) if code == MOUSE_FORWARD => "󰍽 󰁔".to_string(),
+            Some(code) => format!("󰍽 btn{}", code),
+            None => raw.to_string(),
+        },
+    }
+}
+
+/// Formats a modifier key's `icon` and `word` (e.g. `"⇧"`, `"shift"`) according to
+/// `style`, returning the `(icon, label)` pair to display.
+///
+/// Non-modifier keys are returned unchanged.
+pub fn format_modifier_label(icon: &str, word: &str, style: ModifierStyle) -> (String, String) {
+    let is_modifier = !icon.is_empty()
+        && (category_for_key(word) == KeyCategory::Modifier || word.eq_ignore_ascii_case("meta"));
+    if !is_modifier {
+        return (icon.to_string(), word.to_string());
+    }
+
+    match style {
+        ModifierStyle::Both => (icon.to_string(), word.to_string()),
+        ModifierStyle::Glyph => (String::new(), icon.to_string()),
+        ModifierStyle::Word => {
+            let mut chars = word.chars();
+            let capitalized = chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default();
+            (String::new(), capitalized)
+        }
+    }
+}
+
+/// Returns the modifier name (`shift`, `control`, `alt`, `meta`) if `label` is a
+/// modifier that combines with other keys into a chord, e.g. `"⇧ shift"` or `"ShiftLeft"`.
+///
+/// Lock and toggle keys (Caps Lock, Num Lock, Tab) are not chord modifiers.
+pub fn chord_modifier(label: &str) -> Option<&'static str> {
+    let word = normalize_key_label(label).rsplit(' ').next()?;
+    match word.to_ascii_lowercase().as_str() {
+        "shift" => Some("shift"),
+        "control" | "ctrl" => Some("control"),
+        "alt" => Some("alt"),
+        "meta" => Some("meta"),
+        _ => None,
     }
 }
 
+/// Returns a plain-word stand-in for a label that is only a glyph, for fonts (or
+/// `glyphs = false` setups) that can't show it.
+pub fn glyph_word(glyph: &str) -> Option<&'static str> {
+    Some(match glyph {
+        "⇧" => "shift",
+        "⌃" => "control",
+        "⌥" => "alt",
+        "⇪" => "caps",
+        "\u{e62a}" => "meta",
+        "󰁍" => "mouse4",
+        "󰁔" => "mouse5",
+        _ => return None,
+    })
+}
+
+/// Button code rdev reports for the "back" side button.
+#[cfg(target_os = "windows")]
+const MOUSE_BACK: u8 = 1;
+#[cfg(not(target_os = "windows"))]
+const MOUSE_BACK: u8 = 8;
+
+/// Button code rdev reports for the "forward" side button.
+#[cfg(target_os = "windows")]
+const MOUSE_FORWARD: u8 = 2;
+#[cfg(not(target_os = "windows"))]
+const MOUSE_FORWARD: u8 = 9;
+
 /// Maps internal rdev key names to user-friendly or symbolic labels for display.
 ///
 /// # Arguments
@@ -168,10 +264,74 @@ pub fn resolve_key_label(key: Key, layout: &KeyboardLayout) -> String {
     match layout {
         KeyboardLayout::UnitedKingdom => resolve_uk_label(key),
         KeyboardLayout::UnitedStates => resolve_us_label(key),
+        KeyboardLayout::Spain => resolve_es_label(key),
+        KeyboardLayout::Italy => resolve_it_label(key),
+        KeyboardLayout::Sweden | KeyboardLayout::Norway | KeyboardLayout::Denmark => {
+            resolve_nordic_label(key, layout)
+        }
         _ => resolve_us_label(key), // fallback
     }
 }
 
+/// Resolves the unshifted label for keys whose printed symbol differs from the US
+/// position on the given layout (e.g. `ñ` on Spanish keyboards).
+///
+/// Returns `None` when the physical (US) label is correct, so callers fall back to
+/// `resolve_physical_key`.
+pub fn resolve_unshifted_label(key: Key, layout: &KeyboardLayout) -> Option<String> {
+    use Key::*;
+    let label = match (layout, key) {
+        (KeyboardLayout::Spain, BackQuote) => "º",
+        (KeyboardLayout::Spain, Minus) => "'",
+        (KeyboardLayout::Spain, Equal) => "¡",
+        (KeyboardLayout::Spain, LeftBracket) => "`",
+        (KeyboardLayout::Spain, RightBracket) => "+",
+        (KeyboardLayout::Spain, SemiColon) => "Ñ",
+        (KeyboardLayout::Spain, Quote) => "´",
+        (KeyboardLayout::Spain, BackSlash) => "Ç",
+        (KeyboardLayout::Spain, Slash) => "-",
+        (KeyboardLayout::Spain, IntlBackslash) => "<",
+
+        (KeyboardLayout::Italy, BackQuote) => "\\",
+        (KeyboardLayout::Italy, Minus) => "'",
+        (KeyboardLayout::Italy, Equal) => "ì",
+        (KeyboardLayout::Italy, LeftBracket) => "è",
+        (KeyboardLayout::Italy, RightBracket) => "+",
+        (KeyboardLayout::Italy, SemiColon) => "ò",
+        (KeyboardLayout::Italy, Quote) => "à",
+        (KeyboardLayout::Italy, BackSlash) => "ù",
+        (KeyboardLayout::Italy, Slash) => "-",
+        (KeyboardLayout::Italy, IntlBackslash) => "<",
+
+        // Nordic layouts share most positions; the letters right of L differ
+        (KeyboardLayout::Sweden, BackQuote) => "§",
+        (KeyboardLayout::Norway, BackQuote) => "|",
+        (KeyboardLayout::Denmark, BackQuote) => "½",
+        (KeyboardLayout::Norway, Equal) => "\\",
+        (KeyboardLayout::Sweden | KeyboardLayout::Denmark, Equal) => "´",
+        (KeyboardLayout::Sweden, SemiColon) => "Ö",
+        (KeyboardLayout::Sweden, Quote) => "Ä",
+        (KeyboardLayout::Norway, SemiColon) => "Ø",
+        (KeyboardLayout::Norway, Quote) => "Æ",
+        (KeyboardLayout::Denmark, SemiColon) => "Æ",
+        (KeyboardLayout::Denmark, Quote) => "Ø",
+        (KeyboardLayout::Sweden | KeyboardLayout::Norway | KeyboardLayout::Denmark, key) => {
+            match key {
+                Minus => "+",
+                LeftBracket => "Å",
+                RightBracket => "¨",
+                BackSlash => "'",
+                Slash => "-",
+                IntlBackslash => "<",
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some(label.to_string())
+}
+
 /// Resolves the correct shifted US keyboard symbol for a key.
 fn resolve_us_label(key: Key) -> String {
     use Key::*;
@@ -216,3 +376,202 @@ fn resolve_uk_label(key: Key) -> String {
         k => normalize_key_label(&format!("{:?}", k)).to_string(),
     }
 }
+
+/// Resolves the correct shifted Spanish (ISO) keyboard symbol for a key.
+///
+/// Dead keys (`` ` ``/`^`, `´`/`¨`) show their spacing accent.
+fn resolve_es_label(key: Key) -> String {
+    use Key::*;
+    match key {
+        BackQuote => "ª".to_string(),
+        Num1 => "!".to_string(),
+        Num2 => "\"".to_string(),
+        Num3 => "·".to_string(),
+        Num4 => "$".to_string(),
+        Num5 => "%".to_string(),
+        Num6 => "&".to_string(),
+        Num7 => "/".to_string(),
+        Num8 => "(".to_string(),
+        Num9 => ")".to_string(),
+        Num0 => "=".to_string(),
+        Minus => "?".to_string(),
+        Equal => "¿".to_string(),
+        LeftBracket => "^".to_string(),
+        RightBracket => "*".to_string(),
+        SemiColon => "Ñ".to_string(),
+        Quote => "¨".to_string(),
+        BackSlash => "Ç".to_string(),
+        Comma => ";".to_string(),
+        Dot => ":".to_string(),
+        Slash => "_".to_string(),
+        IntlBackslash => ">".to_string(),
+        k => normalize_key_label(&format!("{:?}", k)).to_string(),
+    }
+}
+
+/// Resolves the correct shifted Italian keyboard symbol for a key.
+fn resolve_it_label(key: Key) -> String {
+    use Key::*;
+    match key {
+        BackQuote => "|".to_string(),
+        Num1 => "!".to_string(),
+        Num2 => "\"".to_string(),
+        Num3 => "£".to_string(),
+        Num4 => "$".to_string(),
+        Num5 => "%".to_string(),
+        Num6 => "&".to_string(),
+        Num7 => "/".to_string(),
+        Num8 => "(".to_string(),
+        Num9 => ")".to_string(),
+        Num0 => "=".to_string(),
+        Minus => "?".to_string(),
+        Equal => "^".to_string(),
+        LeftBracket => "é".to_string(),
+        RightBracket => "*".to_string(),
+        SemiColon => "ç".to_string(),
+        Quote => "°".to_string(),
+        BackSlash => "§".to_string(),
+        Comma => ";".to_string(),
+        Dot => ":".to_string(),
+        Slash => "_".to_string(),
+        IntlBackslash => ">".to_string(),
+        k => normalize_key_label(&format!("{:?}", k)).to_string(),
+    }
+}
+
+/// Resolves the correct shifted symbol for the Swedish, Norwegian and Danish layouts,
+/// which differ only in the key left of `1` and the letters right of `L`.
+fn resolve_nordic_label(key: Key, layout: &KeyboardLayout) -> String {
+    use Key::*;
+    match (key, layout) {
+        (BackQuote, KeyboardLayout::Sweden) => "½".to_string(),
+        (BackQuote, _) => "§".to_string(),
+        (SemiColon | Quote, _) => resolve_unshifted_label(key, layout).unwrap_or_default(),
+        (Num1, _) => "!".to_string(),
+        (Num2, _) => "\"".to_string(),
+        (Num3, _) => "#".to_string(),
+        (Num4, _) => "¤".to_string(),
+        (Num5, _) => "%".to_string(),
+        (Num6, _) => "&".to_string(),
+        (Num7, _) => "/".to_string(),
+        (Num8, _) => "(".to_string(),
+        (Num9, _) => ")".to_string(),
+        (Num0, _) => "=".to_string(),
+        (Minus, _) => "?".to_string(),
+        (Equal, _) => "`".to_string(),
+        (LeftBracket, _) => "Å".to_string(),
+        (RightBracket, _) => "^".to_string(),
+        (BackSlash, _) => "*".to_string(),
+        (Comma, _) => ";".to_string(),
+        (Dot, _) => ":".to_string(),
+        (Slash, _) => "_".to_string(),
+        (IntlBackslash, _) => ">".to_string(),
+        (k, _) => normalize_key_label(&format!("{:?}", k)).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys that print a digit or symbol on at least one supported layout.
+    const SYMBOL_KEYS: [Key; 21] = [
+        Key::Num0,
+        Key::Num1,
+        Key::Num2,
+        Key::Num3,
+        Key::Num4,
+        Key::Num5,
+        Key::Num6,
+        Key::Num7,
+        Key::Num8,
+        Key::Num9,
+        Key::Minus,
+        Key::Equal,
+        Key::LeftBracket,
+        Key::RightBracket,
+        Key::SemiColon,
+        Key::Quote,
+        Key::BackQuote,
+        Key::BackSlash,
+        Key::IntlBackslash,
+        Key::Comma,
+        Key::Dot,
+    ];
+
+    #[test]
+    fn every_printable_ascii_char_has_a_category() {
+        for c in '!'..='~' {
+            let expected = if c.is_ascii_alphabetic() {
+                KeyCategory::Normal
+            } else if c.is_ascii_digit() {
+                KeyCategory::Numeric
+            } else {
+                KeyCategory::Symbol
+            };
+            assert_eq!(category_for_key(&c.to_string()), expected, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn currency_and_altgr_symbols_are_symbols() {
+        for symbol in ["€", "¥", "£", "$", "°", "±", "×", "÷", "²", "«", "»"] {
+            assert_eq!(category_for_key(symbol), KeyCategory::Symbol, "{symbol:?}");
+        }
+    }
+
+    #[test]
+    fn normalized_symbol_keys_are_symbols() {
+        for raw in [
+            "Comma",
+            "Period",
+            "Dot",
+            "SemiColon",
+            "Colon",
+            "BackQuote",
+            "Apostrophe",
+            "Minus",
+            "Equal",
+            "Slash",
+            "BackSlash",
+            "IntlBackslash",
+            "Grave",
+            "LeftBracket",
+            "RightBracket",
+            "Quote",
+        ] {
+            let label = normalize_key_label(raw);
+            assert_eq!(
+                category_for_key(label),
+                KeyCategory::Symbol,
+                "{raw} -> {label:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn layout_symbols_are_never_unknown() {
+        let layouts = [
+            KeyboardLayout::UnitedStates,
+            KeyboardLayout::UnitedKingdom,
+            KeyboardLayout::Spain,
+            KeyboardLayout::Italy,
+            KeyboardLayout::Sweden,
+            KeyboardLayout::Norway,
+            KeyboardLayout::Denmark,
+        ];
+        for layout in &layouts {
+            for key in SYMBOL_KEYS {
+                let shifted = resolve_key_label(key, layout);
+                let unshifted = resolve_unshifted_label(key, layout);
+                for label in std::iter::once(shifted).chain(unshifted) {
+                    assert_ne!(
+                        category_for_key(&label),
+                        KeyCategory::Unknown,
+                        "{key:?} on {layout:?} -> {label:?}"
+                    );
+                }
+            }
+        }
+    }
+}
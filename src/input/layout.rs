@@ -1,12 +1,61 @@
+use std::fmt;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyboardLayout {
     UnitedStates,
     UnitedKingdom,
+    Spain,
+    Italy,
+    Sweden,
+    Norway,
+    Denmark,
     Other(u16),
 }
 
+impl KeyboardLayout {
+    /// Returns the XKB/`setxkbmap` name of a known layout, or `None` for `Other`.
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            KeyboardLayout::UnitedStates => Some("us"),
+            KeyboardLayout::UnitedKingdom => Some("gb"),
+            KeyboardLayout::Spain => Some("es"),
+            KeyboardLayout::Italy => Some("it"),
+            KeyboardLayout::Sweden => Some("se"),
+            KeyboardLayout::Norway => Some("no"),
+            KeyboardLayout::Denmark => Some("dk"),
+            KeyboardLayout::Other(_) => None,
+        }
+    }
+
+    /// Parses a layout name as used by `setxkbmap` (e.g. `"us"`, `"gb"`), for the
+    /// `layout` config override. Returns `None` for layouts without a symbol map.
+    pub fn from_name(name: &str) -> Option<KeyboardLayout> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "us" => Some(KeyboardLayout::UnitedStates),
+            "gb" | "uk" => Some(KeyboardLayout::UnitedKingdom),
+            "es" => Some(KeyboardLayout::Spain),
+            "it" => Some(KeyboardLayout::Italy),
+            "se" | "fi" => Some(KeyboardLayout::Sweden),
+            "no" => Some(KeyboardLayout::Norway),
+            "dk" => Some(KeyboardLayout::Denmark),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KeyboardLayout {
+    /// Short layout name; unknown layouts show their raw id so users can report it.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.name(), self) {
+            (Some(name), _) => write!(f, "{name}"),
+            (None, KeyboardLayout::Other(id)) => write!(f, "other (0x{:04X})", id),
+            (None, _) => write!(f, "unknown"),
+        }
+    }
+}
+
 // Platform-specific layout detection
-/*#[cfg(target_os = "windows")]
+#[cfg(target_os = "windows")]
 pub use crate::platform::windows::layout::detect_layout;
 
 #[cfg(target_os = "linux")]
@@ -14,4 +63,3 @@ pub use crate::platform::linux::layout::detect_layout;
 
 #[cfg(target_os = "macos")]
 pub use crate::platform::macos::layout::detect_layout;
-*/
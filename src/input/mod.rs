@@ -1,4 +1,8 @@
+pub mod broadcast;
+pub mod focus;
 pub mod input;
 pub mod keyboard;
 pub mod keymap;
 pub mod layout;
+pub mod replay;
+pub mod unmapped;
@@ -0,0 +1,95 @@
+use crate::input::input::{InputEvent, KeyId};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Records every event from `rx` to `path`, one per line, on a background thread.
+///
+/// Each line has the form `<ms since start>\t<key|release|mouse>\t<label>`, which is what
+/// [`replay_events`] reads back. Key presses and releases add a fourth `\t<key id>` column.
+pub fn record_events(path: PathBuf, rx: Receiver<InputEvent>) {
+    let file = match File::create(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            warn!("Failed to create recording '{}': {e}", path.display());
+            return;
+        }
+    };
+
+    thread::spawn(move || {
+        let mut out = BufWriter::new(file);
+        let start = Instant::now();
+
+        for event in rx.iter() {
+            let (kind, label) = match &event {
+                InputEvent::KeyPress(id, label) => ("key", format!("{label}\t{}", id.0)),
+                InputEvent::KeyRelease(id, label) => ("release", format!("{label}\t{}", id.0)),
+                InputEvent::MouseClick(label) => ("mouse", label.clone()),
+            };
+
+            let line = format!("{}\t{}\t{}", start.elapsed().as_millis(), kind, label);
+            // Flush per event so the recording survives the app being killed
+            if writeln!(out, "{line}").and_then(|_| out.flush()).is_err() {
+                warn!("Failed to write recording '{}'", path.display());
+                return;
+            }
+        }
+    });
+}
+
+/// Replays a recording made by [`record_events`], sending each event to `tx` with its
+/// original timing. Blocks until the recording has been played.
+///
+/// Malformed lines are skipped with a warning.
+pub fn replay_events(path: &Path, tx: Sender<InputEvent>) {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to read recording '{}': {e}", path.display());
+            return;
+        }
+    };
+
+    let start = Instant::now();
+
+    for (n, line) in content.lines().enumerate() {
+        let Some((at, event)) = parse_line(line) else {
+            warn!("Skipping malformed line {} in recording", n + 1);
+            continue;
+        };
+
+        // Sleep until the event's original offset from the start
+        if let Some(wait) = at.checked_sub(start.elapsed()) {
+            thread::sleep(wait);
+        }
+
+        if tx.send(event).is_err() {
+            return;
+        }
+    }
+}
+
+/// Parses a single `<ms>\t<kind>\t<label>[\t<key id>]` recording line.
+///
+/// Key events recorded before key ids existed use their label as the id.
+fn parse_line(line: &str) -> Option<(Duration, InputEvent)> {
+    let mut parts = line.splitn(4, '\t');
+    let at = Duration::from_millis(parts.next()?.parse().ok()?);
+    let kind = parts.next()?;
+    let label = parts.next()?.to_string();
+    let id = parts.next().map(str::to_string);
+
+    let event = match kind {
+        "key" => InputEvent::KeyPress(KeyId(id.unwrap_or_else(|| label.clone())), label),
+        "release" => InputEvent::KeyRelease(KeyId(id.unwrap_or_else(|| label.clone())), label),
+        "mouse" => InputEvent::MouseClick(label),
+        _ => return None,
+    };
+
+    Some((at, event))
+}
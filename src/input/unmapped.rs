@@ -0,0 +1,67 @@
+use crate::input::input::KeyId;
+use crate::input::layout::KeyboardLayout;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// File unmapped keys are appended to, next to the config file.
+const UNMAPPED_LOG: &str = "unmapped.log";
+
+/// Returns where `log_unmapped` writes, given the path of the loaded config file.
+pub fn unmapped_log_path(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(UNMAPPED_LOG)
+}
+
+/// Appends a key that resolved to the `Unknown` category to `path`, for `log_unmapped`.
+///
+/// Each line has the form `<key id>\t<label>\t<layout>`, e.g. `Unknown(171)\tUnknown(171)\tus`,
+/// ready to paste into an issue. Keys already in the file are skipped, so each key is
+/// logged once no matter how often it's pressed or how many overlays are running.
+///
+/// # Returns
+/// `true` if a line was written.
+pub fn log_unmapped(
+    path: &Path,
+    id: &KeyId,
+    label: &str,
+    layout: Option<KeyboardLayout>,
+) -> io::Result<bool> {
+    let prefix = format!("{}\t", id.0);
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    if existing.lines().any(|line| line.starts_with(&prefix)) {
+        return Ok(false);
+    }
+
+    let layout = layout.map_or("unknown".to_string(), |l| l.to_string());
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{prefix}{label}\t{layout}")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempPath;
+
+    #[test]
+    fn each_key_is_logged_once() {
+        let path = TempPath::new("log");
+        let key = KeyId("Unknown(171)".into());
+        let layout = Some(KeyboardLayout::UnitedStates);
+
+        assert!(log_unmapped(&path, &key, "Unknown(171)", layout).unwrap());
+        assert!(!log_unmapped(&path, &key, "Unknown(171)", layout).unwrap());
+        assert!(log_unmapped(&path, &KeyId("Unknown(172)".into()), "Unknown(172)", None).unwrap());
+
+        let lines = fs::read_to_string(&*path).unwrap();
+        assert_eq!(
+            lines,
+            "Unknown(171)\tUnknown(171)\tus\nUnknown(172)\tUnknown(172)\tunknown\n"
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! FerrisKeys shows keystrokes and mouse clicks in a transparent overlay.
+//!
+//! The `ferriskeys` binary is a thin wrapper over [`run`], which it calls with the parsed
+//! [`Args`]. To build on FerrisKeys, e.g. to embed the overlay in your own egui app, use the
+//! types re-exported here:
+//!
+//! - [`Config`] loads and hot-reloads `config.toml`, with a [`Style`] per [`KeyCategory`];
+//!   [`Config::in_memory`] gives the defaults without any file. The types of its settings,
+//!   like [`Trigger`] or [`Hotkey`], are exported too.
+//! - [`VisualiserApp`] is the overlay itself; drive it with [`VisualiserApp::push_event`]
+//!   and paint it into your own UI with [`VisualiserApp::show`]. Its settings are reached
+//!   through [`VisualiserApp::config_mut`], and the keys shown through
+//!   [`VisualiserApp::key_buffer`].
+//! - [`KeyBuffer`] holds the keys on screen and lays them out.
+//! - [`InputEvent`] and [`KeyId`] describe input, and [`category_for_key`] and
+//!   [`resolve_physical_key`] turn keys into labels and categories.
+//!
+//! Everything else is internal and may change between releases.
+
+// `logging` comes first so its macros are visible in the rest
+#[macro_use]
+mod logging;
+mod app;
+mod cli;
+mod config;
+mod core;
+mod error;
+mod input;
+mod platform;
+#[cfg(test)]
+mod test_util;
+mod ui;
+
+pub use app::run;
+pub use cli::{version, Args, Command, USAGE};
+pub use config::config::{
+    Anchor, Animation, Config, DisplayMode, EmptyLabel, GradientDirection, Hotkey, IconPosition,
+    Modifier, ModifierStyle, Overflow, Panel, PositionOrigin, RenderMode, RowAlign, Shadow,
+    SortSimultaneous, SoundConfig, Style, TextOutline, Trigger,
+};
+pub use core::buffer::{KeyBuffer, KeyEntry};
+pub use error::FerrisKeysError;
+pub use input::input::{InputEvent, KeyId};
+pub use input::keyboard::resolve_physical_key;
+pub use input::keymap::{category_for_key, KeyCategory};
+pub use input::layout::KeyboardLayout;
+pub use logging::Level;
+pub use ui::visualiser::VisualiserApp;
@@ -0,0 +1,110 @@
+//! A tiny leveled logger for diagnostics, so services can choose how much FerrisKeys says.
+//!
+//! Messages go to stderr with a level prefix, e.g. `[WARN] Invalid anchor 'up'`. Use the
+//! `error!`, `warn!`, `info!` and `debug!` macros; the threshold is set once at startup
+//! from `--log-level` or `--verbose`. Only errors and warnings are printed by default, so
+//! normal runs stay quiet.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much diagnostic output to print; each level includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Level {
+    /// Print nothing.
+    Off,
+    /// Only failures that stop a feature from working.
+    Error,
+    /// Also problems FerrisKeys works around, like invalid config values (the default).
+    #[default]
+    Warn,
+    /// Also notable events, like creating the config file.
+    Info,
+    /// Also details for troubleshooting, like the config paths searched.
+    Debug,
+}
+
+impl Level {
+    /// Parses a level name (`off`, `error`, `warn`, `info` or `debug`), ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "off" => Some(Level::Off),
+            "error" => Some(Level::Error),
+            "warn" | "warning" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+
+    /// The tag printed before each message of this level.
+    fn prefix(self) -> &'static str {
+        match self {
+            Level::Off => "",
+            Level::Error => "[ERROR]",
+            Level::Warn => "[WARN]",
+            Level::Info => "[INFO]",
+            Level::Debug => "[DEBUG]",
+        }
+    }
+}
+
+/// The most verbose level printed.
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+/// Sets the most verbose level that is printed.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Returns whether messages of `level` are printed.
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Prints a message if its level is enabled. Called by the logging macros.
+pub fn write(level: Level, args: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("{} {}", level.prefix(), args);
+    }
+}
+
+/// Logs a failure that stops a feature from working.
+macro_rules! error {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Error, format_args!($($arg)*))
+    };
+}
+
+/// Logs a problem FerrisKeys works around, such as an invalid config value.
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Warn, format_args!($($arg)*))
+    };
+}
+
+/// Logs a notable event.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Info, format_args!($($arg)*))
+    };
+}
+
+/// Logs a detail that only matters when troubleshooting.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_parse_and_order_by_verbosity() {
+        assert_eq!(Level::parse("WARN"), Some(Level::Warn));
+        assert_eq!(Level::parse("verbose"), None);
+        assert!(Level::Error < Level::Warn && Level::Info < Level::Debug);
+    }
+}
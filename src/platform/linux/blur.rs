@@ -0,0 +1,35 @@
+use raw_window_handle::RawWindowHandle;
+use std::process::Command;
+
+/// KWin's hint asking for the area behind a window to be blurred; an empty region means the
+/// whole window.
+const KDE_BLUR_HINT: &str = "_KDE_NET_WM_BLUR_BEHIND_REGION";
+
+/// Asks the compositor to blur behind an X11 window, using `xprop` to set KWin's blur hint
+/// (or to remove it, when `enabled` is false):
+/// ```text
+/// xprop -id 79691778 -f _KDE_NET_WM_BLUR_BEHIND_REGION 32c -set _KDE_NET_WM_BLUR_BEHIND_REGION 0
+/// ```
+///
+/// KDE Plasma (including its XWayland windows) honours the hint; other compositors ignore it.
+/// Native Wayland windows would need KWin's `org_kde_kwin_blur` protocol instead, which
+/// isn't implemented, so they are never blurred.
+///
+/// # Returns
+/// `false` if the window isn't an X11 window or `xprop` is missing or fails.
+pub fn set_blur(window: RawWindowHandle, enabled: bool) -> bool {
+    let id = match window {
+        RawWindowHandle::Xlib(handle) => handle.window.to_string(),
+        RawWindowHandle::Xcb(handle) => handle.window.get().to_string(),
+        _ => return false,
+    };
+
+    let mut xprop = Command::new("xprop");
+    xprop.args(["-id", &id]);
+    if enabled {
+        xprop.args(["-f", KDE_BLUR_HINT, "32c", "-set", KDE_BLUR_HINT, "0"]);
+    } else {
+        xprop.args(["-remove", KDE_BLUR_HINT]);
+    }
+    xprop.status().is_ok_and(|status| status.success())
+}
@@ -0,0 +1,46 @@
+use crate::input::focus::FocusedWindow;
+use std::process::Command;
+
+/// Returns the focused window's class and title on X11 using `xprop`.
+///
+/// The active window id is read from the root window's `_NET_ACTIVE_WINDOW` property, then that
+/// window's `WM_CLASS` and `_NET_WM_NAME` are queried:
+/// ```text
+/// WM_CLASS(STRING) = "code", "Code"
+/// _NET_WM_NAME(UTF8_STRING) = "main.rs - FerrisKeys - Visual Studio Code"
+/// ```
+///
+/// # Returns
+/// `None` if `xprop` is missing, no window is focused, or the session is not X11 (most Wayland
+/// compositors don't expose the focused window to other clients).
+pub fn focused_window() -> Option<FocusedWindow> {
+    let root = xprop(&["-root", "_NET_ACTIVE_WINDOW"])?;
+    let id = root.split_whitespace().last()?;
+    if id == "0x0" {
+        return None;
+    }
+
+    let props = xprop(&["-id", id, "WM_CLASS", "_NET_WM_NAME"])?;
+    let mut window = FocusedWindow::default();
+    for line in props.lines() {
+        let Some((name, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        if name.starts_with("WM_CLASS") {
+            // Instance and class names, e.g. `"code", "Code"`; keep both for matching
+            window.class = value.replace('"', "");
+        } else if name.starts_with("_NET_WM_NAME") {
+            window.title = value.trim_matches('"').to_string();
+        }
+    }
+    Some(window)
+}
+
+/// Runs `xprop` with `args` and returns its output.
+fn xprop(args: &[&str]) -> Option<String> {
+    let output = Command::new("xprop").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
@@ -1,6 +1,10 @@
-use crate::input::input::InputEvent;
-use crate::input::{keyboard::resolve_physical_key, keymap::resolve_key_label};
-use crate::platform::linux::layout::detect_layout;
+use crate::input::input::{record_cursor, report_listener_failure, InputEvent, KeyId};
+use crate::input::layout::KeyboardLayout;
+use crate::input::{
+    keyboard::resolve_physical_key,
+    keymap::{resolve_key_label, resolve_unshifted_label},
+};
+use crate::platform::linux::xkb::{ComposeStep, XkbResolver};
 use rdev::{listen, EventType, Key};
 use std::{
     sync::{
@@ -9,13 +13,17 @@ use std::{
         Arc,
     },
     thread,
+    time::{Duration, Instant},
 };
 
 /// Starts the Linux input event listener in a background thread.
 ///
 /// Listens to global key and mouse events using `rdev::listen()`, then:
-/// - Resolves key labels based on the current keyboard layout.
+/// - Resolves key labels through the active XKB keymap, falling back to the built-in
+///   layout maps when libxkbcommon is unavailable.
 /// - Tracks the Shift key status manually to support shifted characters.
+/// - With `compose`, holds back the keys of a Compose sequence (e.g. `Compose`, `'`, `e`)
+///   and sends the composed text (`é`) as a single press instead.
 /// - Sends processed input events (keyboard or mouse) to the main application
 ///   via the given `Sender<InputEvent>`.
 ///
@@ -23,28 +31,76 @@ use std::{
 ///
 /// # Arguments
 /// * `tx` - A channel `Sender` to push `InputEvent` messages to the application.
-pub fn start_input_listener(tx: Sender<InputEvent>) {
-    let layout = detect_layout(); // Detect the active keyboard layout once at startup
-
+/// * `layout` - Keyboard layout used to resolve shifted symbols.
+/// * `compose` - Whether to combine Compose sequences into the character they produce.
+pub fn start_input_listener(tx: Sender<InputEvent>, layout: KeyboardLayout, compose: bool) {
     let shift_down = Arc::new(AtomicBool::new(false)); // Shared state to track Shift press
     let shift_flag = shift_down.clone(); // Clone for use inside event handler
 
+    let mouse_down = Arc::new(AtomicBool::new(false)); // Shared state to track a held mouse button
+    let mouse_flag = mouse_down.clone(); // Clone for use inside event handler
+    let mut last_drag: Option<Instant> = None; // Throttles drag events while the mouse moves
+
     thread::spawn(move || {
+        // Built on the listener thread, which is the only place it is used
+        let xkb = XkbResolver::new(&layout);
+        let mut composer = xkb
+            .as_ref()
+            .filter(|_| compose)
+            .and_then(XkbResolver::composer);
+        if compose && composer.is_none() {
+            warn!("Compose needs libxkbcommon and a Compose table. Showing keys as typed.");
+        }
+        let mut pending: Vec<(KeyId, String)> = Vec::new(); // Keys of an unfinished Compose sequence
+
         if let Err(err) = listen(move |event| match event.event_type {
             // Handle key press
             EventType::KeyPress(key) => {
                 if key == Key::ShiftLeft || key == Key::ShiftRight {
                     shift_flag.store(true, Ordering::SeqCst);
-                    tx.send(InputEvent::KeyPress("⇧ shift".into())).ok();
+                    tx.send(InputEvent::KeyPress(KeyId::of(key), "⇧ shift".into()))
+                        .ok();
                 } else {
-                    // Resolve label based on shift state and layout
-                    let label = if shift_flag.load(Ordering::SeqCst) {
-                        resolve_key_label(key, &layout)
-                    } else {
-                        resolve_physical_key(key)
-                    };
+                    // Resolve label based on shift state and layout, preferring XKB
+                    let shifted = shift_flag.load(Ordering::SeqCst);
+                    let label = xkb
+                        .as_ref()
+                        .and_then(|xkb| xkb.resolve(key, shifted))
+                        .unwrap_or_else(|| {
+                            if shifted {
+                                resolve_key_label(key, &layout)
+                            } else {
+                                resolve_unshifted_label(key, &layout)
+                                    .unwrap_or_else(|| resolve_physical_key(key))
+                            }
+                        });
+
+                    let id = KeyId::of(key);
 
-                    tx.send(InputEvent::KeyPress(label)).ok();
+                    // Keys in a Compose sequence are held back until it produces its text
+                    let keysym = xkb.as_ref().and_then(|xkb| xkb.keysym(key, shifted));
+                    if let (Some(composer), Some(keysym)) = (composer.as_mut(), keysym) {
+                        match composer.feed(keysym) {
+                            ComposeStep::Idle => {}
+                            ComposeStep::Composing => {
+                                pending.push((id, label));
+                                return;
+                            }
+                            ComposeStep::Composed(text) => {
+                                pending.clear();
+                                tx.send(InputEvent::KeyPress(id, text)).ok();
+                                return;
+                            }
+                            ComposeStep::Cancelled => {
+                                // Not a known sequence: show what was typed after all
+                                for (id, label) in pending.drain(..) {
+                                    tx.send(InputEvent::KeyPress(id, label)).ok();
+                                }
+                            }
+                        }
+                    }
+
+                    tx.send(InputEvent::KeyPress(id, label)).ok();
                 }
             }
 
@@ -52,22 +108,48 @@ pub fn start_input_listener(tx: Sender<InputEvent>) {
             EventType::KeyRelease(key) => {
                 if key == Key::ShiftLeft || key == Key::ShiftRight {
                     shift_flag.store(false, Ordering::SeqCst);
-                } else {
-                    let _raw = format!("{:?}", key);
-                    // Debug logging can be inserted here if needed
                 }
+
+                // Releases use the physical label; they only need to identify the key
+                tx.send(InputEvent::KeyRelease(
+                    KeyId::of(key),
+                    resolve_physical_key(key),
+                ))
+                .ok();
             }
 
             // Handle mouse button press
             EventType::ButtonPress(button) => {
+                mouse_flag.store(true, Ordering::SeqCst);
                 let label = format!("Mouse{:?}", button);
                 tx.send(InputEvent::MouseClick(label)).ok();
             }
 
-            // Ignore other events (e.g., mouse move, scroll, etc.)
+            // Handle mouse button release (ends any drag)
+            EventType::ButtonRelease(_) => {
+                mouse_flag.store(false, Ordering::SeqCst);
+                last_drag = None;
+            }
+
+            EventType::MouseMove { x, y } => {
+                record_cursor(x, y);
+
+                // Moving with a button held is a drag; re-send periodically so it stays visible
+                if mouse_flag.load(Ordering::SeqCst)
+                    && last_drag.is_none_or(|t| t.elapsed() >= Duration::from_millis(100))
+                {
+                    last_drag = Some(Instant::now());
+                    tx.send(InputEvent::MouseClick("󰍽 drag".into())).ok();
+                }
+            }
+
+            // Ignore other events (e.g., scroll)
             _ => {}
         }) {
-            eprintln!("Failed to listen to keyboard events: {:?}", err);
+            report_listener_failure(format!(
+                "Input capture failed ({err:?}). FerrisKeys needs an X11 session (or XWayland) \
+                 to read keys; on Wayland, log in with an X11 session instead."
+            ));
         }
     });
 }
@@ -1,10 +1,11 @@
+use crate::error::FerrisKeysError;
 use crate::input::layout::KeyboardLayout;
 use std::process::Command;
 
 /// Detects the active keyboard layout on Linux using the `setxkbmap -query` command.
 ///
 /// The function attempts to run `setxkbmap -query`, which outputs lines like:
-/// ```
+/// ```text
 /// layout:     us,gb
 /// variant:    ,
 /// ```
@@ -14,32 +15,49 @@ use std::process::Command;
 /// Currently supports:
 /// - `"gb"` → `KeyboardLayout::UnitedKingdom`
 /// - `"us"` → `KeyboardLayout::UnitedStates`
+/// - `"es"` → `KeyboardLayout::Spain`
+/// - `"it"` → `KeyboardLayout::Italy`
+/// - `"se"`, `"fi"` → `KeyboardLayout::Sweden` (Finnish shares the Swedish layout)
+/// - `"no"` → `KeyboardLayout::Norway`
+/// - `"dk"` → `KeyboardLayout::Denmark`
 ///
 /// Unknown layouts are returned as `KeyboardLayout::Other(0)`. The `0` is a placeholder and may be
 /// enhanced later to carry actual layout IDs or hashes.
 ///
 /// # Returns
-/// A `KeyboardLayout` enum corresponding to the active layout, or a fallback if detection fails.
-pub fn detect_layout() -> KeyboardLayout {
-    if let Ok(output) = Command::new("setxkbmap").arg("-query").output() {
-        if let Ok(stdout) = String::from_utf8(output.stdout) {
-            for line in stdout.lines() {
-                if line.starts_with("layout:") {
-                    let layout = line.split(':').nth(1).unwrap_or("").trim();
+/// A `KeyboardLayout` enum corresponding to the active layout.
+///
+/// # Errors
+/// Returns `FerrisKeysError::LayoutDetection` if `setxkbmap` is missing or doesn't report a
+/// layout.
+pub fn detect_layout() -> Result<KeyboardLayout, FerrisKeysError> {
+    let layout = setxkbmap_query("layout").ok_or_else(|| {
+        FerrisKeysError::LayoutDetection("`setxkbmap -query` reported no layout".into())
+    })?;
 
-                    // If multiple layouts are listed (e.g., "gb,us"), use the first one
-                    let primary_layout = layout.split(',').next().unwrap_or("").trim();
+    // If multiple layouts are listed (e.g., "gb,us"), use the first one
+    let primary_layout = layout.split(',').next().unwrap_or("").trim();
+
+    Ok(match primary_layout {
+        "gb" => KeyboardLayout::UnitedKingdom,
+        "us" => KeyboardLayout::UnitedStates,
+        "es" => KeyboardLayout::Spain,
+        "it" => KeyboardLayout::Italy,
+        "se" | "fi" => KeyboardLayout::Sweden,
+        "no" => KeyboardLayout::Norway,
+        "dk" => KeyboardLayout::Denmark,
+        _ => KeyboardLayout::Other(0), // Unrecognized layout
+    })
+}
 
-                    return match primary_layout {
-                        "gb" => KeyboardLayout::UnitedKingdom,
-                        "us" => KeyboardLayout::UnitedStates,
-                        _ => KeyboardLayout::Other(0), // Unrecognized layout
-                    };
-                }
-            }
-        }
-    }
+/// Returns the value of one field (e.g. `"layout"`, `"variant"`) from `setxkbmap -query`,
+/// or `None` if the command fails or doesn't report it.
+pub fn setxkbmap_query(field: &str) -> Option<String> {
+    let output = Command::new("setxkbmap").arg("-query").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
 
-    // Fallback if command fails or output is malformed
-    KeyboardLayout::Other(0)
+    stdout.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == field).then(|| value.trim().to_string())
+    })
 }
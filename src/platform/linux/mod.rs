@@ -1,2 +1,6 @@
+pub mod blur;
+pub mod focus;
 pub mod input;
 pub mod layout;
+pub mod monitor;
+pub mod xkb;
@@ -0,0 +1,48 @@
+use crate::ui::monitor::MonitorRect;
+use std::process::Command;
+
+/// Enumerates connected monitors on Linux using the `xrandr --listmonitors` command.
+///
+/// The command outputs lines like:
+/// ```text
+/// Monitors: 2
+///  0: +*DP-1 2560/597x1440/336+0+0  DP-1
+///  1: +HDMI-1 1920/527x1080/296+2560+0  HDMI-1
+/// ```
+///
+/// Each geometry field is parsed as `width/mmxheight/mm+x+y`.
+///
+/// # Returns
+/// A list of monitors in the order `xrandr` reports them, or an empty list if detection fails.
+pub fn detect_monitors() -> Vec<MonitorRect> {
+    let Ok(output) = Command::new("xrandr").arg("--listmonitors").output() else {
+        return Vec::new();
+    };
+
+    String::from_utf8(output.stdout)
+        .map(|stdout| stdout.lines().filter_map(parse_monitor_line).collect())
+        .unwrap_or_default()
+}
+
+/// Parses a single monitor line from `xrandr --listmonitors`.
+fn parse_monitor_line(line: &str) -> Option<MonitorRect> {
+    // The geometry is the third whitespace-separated field, e.g. "2560/597x1440/336+0+0"
+    let geometry = line.split_whitespace().nth(2)?;
+    let (width, rest) = geometry.split_once('x')?;
+    let mut parts = rest.split('+');
+    let height = parts.next()?;
+    let x = parts.next()?.parse::<f32>().ok()?;
+    let y = parts.next()?.parse::<f32>().ok()?;
+
+    // Strip the physical size suffix ("/597") from each dimension
+    let width = width.split('/').next()?.parse::<f32>().ok()?;
+    let height = height.split('/').next()?.parse::<f32>().ok()?;
+
+    // xrandr doesn't report panels, so the work area is the whole monitor
+    Some(MonitorRect {
+        position: [x, y],
+        size: [width, height],
+        work_position: [x, y],
+        work_size: [width, height],
+    })
+}
@@ -0,0 +1,333 @@
+use crate::input::keyboard::typed_label;
+use crate::input::layout::KeyboardLayout;
+use crate::platform::linux::layout::setxkbmap_query;
+use rdev::Key;
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    ptr,
+};
+use xkbcommon_dl::{
+    xkb_compose_compile_flags, xkb_compose_feed_result, xkb_compose_state, xkb_compose_state_flags,
+    xkb_compose_status, xkb_compose_table, xkb_context, xkb_context_flags, xkb_keymap,
+    xkb_keymap_compile_flags, xkb_rule_names, xkb_state, xkbcommon_compose_option,
+    xkbcommon_option, XkbCommon, XkbCommonCompose, XKB_MOD_INVALID, XKB_MOD_NAME_SHIFT,
+};
+
+/// Resolves keys to the text the active XKB keymap produces for them.
+///
+/// Any installed layout (including variants such as Dvorak) gets correct labels this
+/// way, without per-layout Rust code. libxkbcommon is loaded at runtime; if it or the
+/// keymap is unavailable, [`XkbResolver::new`] returns `None` and the listener falls
+/// back to the hardcoded layout maps in `keymap.rs`.
+pub struct XkbResolver {
+    xkb: &'static XkbCommon,
+    context: *mut xkb_context,
+    keymap: *mut xkb_keymap,
+    state: *mut xkb_state,
+    shift_mask: u32,
+}
+
+impl XkbResolver {
+    /// Compiles an XKB keymap for `layout`.
+    ///
+    /// When `layout` is the system layout (or unknown to FerrisKeys), the layout and
+    /// variant reported by `setxkbmap -query` are used so variants are respected; a
+    /// different `layout` (from the config override) is compiled by name.
+    pub fn new(layout: &KeyboardLayout) -> Option<Self> {
+        let xkb = xkbcommon_option()?;
+
+        let system = setxkbmap_query("layout");
+        let system_primary = system
+            .as_deref()
+            .and_then(|l| l.split(',').next())
+            .map(str::trim);
+        let (layout_name, variant) = match layout.name() {
+            Some(name) if system_primary != Some(name) => (name.to_string(), String::new()),
+            _ => (system?, setxkbmap_query("variant").unwrap_or_default()),
+        };
+
+        let layout_c = CString::new(layout_name).ok()?;
+        let variant_c = CString::new(variant).ok()?;
+        let names = xkb_rule_names {
+            rules: ptr::null(),
+            model: ptr::null(),
+            layout: layout_c.as_ptr(),
+            variant: variant_c.as_ptr(),
+            options: ptr::null(),
+        };
+
+        unsafe {
+            let context = (xkb.xkb_context_new)(xkb_context_flags::XKB_CONTEXT_NO_FLAGS);
+            if context.is_null() {
+                return None;
+            }
+
+            let keymap = (xkb.xkb_keymap_new_from_names)(
+                context,
+                &names,
+                xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            );
+            if keymap.is_null() {
+                (xkb.xkb_context_unref)(context);
+                return None;
+            }
+
+            let state = (xkb.xkb_state_new)(keymap);
+            if state.is_null() {
+                (xkb.xkb_keymap_unref)(keymap);
+                (xkb.xkb_context_unref)(context);
+                return None;
+            }
+
+            let shift_index = (xkb.xkb_keymap_mod_get_index)(
+                keymap,
+                XKB_MOD_NAME_SHIFT.as_ptr() as *const c_char,
+            );
+            let shift_mask = match shift_index {
+                XKB_MOD_INVALID => 0,
+                index => 1 << index,
+            };
+
+            Some(Self {
+                xkb,
+                context,
+                keymap,
+                state,
+                shift_mask,
+            })
+        }
+    }
+
+    /// Returns the keysym `key` produces with or without Shift, for feeding a [`Composer`].
+    pub fn keysym(&self, key: Key, shift: bool) -> Option<u32> {
+        let keycode = keycode(key)?;
+        let depressed = if shift { self.shift_mask } else { 0 };
+
+        let sym = unsafe {
+            (self.xkb.xkb_state_update_mask)(self.state, depressed, 0, 0, 0, 0, 0);
+            (self.xkb.xkb_state_key_get_one_sym)(self.state, keycode)
+        };
+        (sym != 0).then_some(sym)
+    }
+
+    /// Loads the Compose sequences for the user's locale (from `LC_ALL`, `LC_CTYPE` or
+    /// `LANG`), or `None` if the compose module or table is unavailable.
+    pub fn composer(&self) -> Option<Composer> {
+        let compose = xkbcommon_compose_option()?;
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_else(|| "C".to_string());
+        let locale = CString::new(locale).ok()?;
+
+        unsafe {
+            let table = (compose.xkb_compose_table_new_from_locale)(
+                self.context,
+                locale.as_ptr(),
+                xkb_compose_compile_flags::XKB_COMPOSE_COMPILE_NO_FLAGS,
+            );
+            if table.is_null() {
+                return None;
+            }
+
+            let state = (compose.xkb_compose_state_new)(
+                table,
+                xkb_compose_state_flags::XKB_COMPOSE_STATE_NO_FLAGS,
+            );
+            if state.is_null() {
+                (compose.xkb_compose_table_unref)(table);
+                return None;
+            }
+
+            Some(Composer {
+                compose,
+                table,
+                state,
+            })
+        }
+    }
+
+    /// Returns the text `key` types with or without Shift, or `None` if it doesn't
+    /// type a printable character (dead keys, non-text keys, unmapped keys).
+    pub fn resolve(&self, key: Key, shift: bool) -> Option<String> {
+        let keycode = text_keycode(key)?;
+        let depressed = if shift { self.shift_mask } else { 0 };
+
+        let mut buf = [0 as c_char; 16];
+        let text = unsafe {
+            (self.xkb.xkb_state_update_mask)(self.state, depressed, 0, 0, 0, 0, 0);
+            let len =
+                (self.xkb.xkb_state_key_get_utf8)(self.state, keycode, buf.as_mut_ptr(), buf.len());
+            if len <= 0 {
+                return None;
+            }
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        };
+
+        typed_label(&text)
+    }
+}
+
+impl Drop for XkbResolver {
+    fn drop(&mut self) {
+        unsafe {
+            (self.xkb.xkb_state_unref)(self.state);
+            (self.xkb.xkb_keymap_unref)(self.keymap);
+            (self.xkb.xkb_context_unref)(self.context);
+        }
+    }
+}
+
+/// Where a Compose sequence stands after feeding it a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposeStep {
+    /// No sequence is in progress; the key should be shown as usual.
+    Idle,
+    /// The key started or continued a sequence; hold it back until the sequence ends.
+    Composing,
+    /// The sequence finished and produced this text.
+    Composed(String),
+    /// The key didn't continue the sequence, which was abandoned.
+    Cancelled,
+}
+
+/// Tracks Compose sequences (e.g. `Compose`, `'`, `e` → `é`) using the locale's
+/// Compose table, as loaded by [`XkbResolver::composer`].
+pub struct Composer {
+    compose: &'static XkbCommonCompose,
+    table: *mut xkb_compose_table,
+    state: *mut xkb_compose_state,
+}
+
+impl Composer {
+    /// Feeds the keysym of a pressed key into the sequence.
+    pub fn feed(&mut self, keysym: u32) -> ComposeStep {
+        unsafe {
+            let result = (self.compose.xkb_compose_state_feed)(self.state, keysym);
+            if result == xkb_compose_feed_result::XKB_COMPOSE_FEED_IGNORED {
+                return ComposeStep::Idle;
+            }
+
+            match (self.compose.xkb_compose_state_get_status)(self.state) {
+                xkb_compose_status::XKB_COMPOSE_NOTHING => ComposeStep::Idle,
+                xkb_compose_status::XKB_COMPOSE_COMPOSING => ComposeStep::Composing,
+                xkb_compose_status::XKB_COMPOSE_CANCELLED => {
+                    (self.compose.xkb_compose_state_reset)(self.state);
+                    ComposeStep::Cancelled
+                }
+                xkb_compose_status::XKB_COMPOSE_COMPOSED => {
+                    let mut buf = [0 as c_char; 64];
+                    let len = (self.compose.xkb_compose_state_get_utf8)(
+                        self.state,
+                        buf.as_mut_ptr(),
+                        buf.len(),
+                    );
+                    (self.compose.xkb_compose_state_reset)(self.state);
+                    if len <= 0 {
+                        return ComposeStep::Cancelled;
+                    }
+                    let text = CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned();
+                    ComposeStep::Composed(text)
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Composer {
+    fn drop(&mut self) {
+        unsafe {
+            (self.compose.xkb_compose_state_unref)(self.state);
+            (self.compose.xkb_compose_table_unref)(self.table);
+        }
+    }
+}
+
+/// Returns the X11 keycode of any key, for keysym lookups.
+///
+/// Covers the typing area plus the keys commonly bound to Compose (Menu arrives as
+/// `Unknown(135)`, which already carries its keycode).
+fn keycode(key: Key) -> Option<u32> {
+    use Key::*;
+    text_keycode(key).or(Some(match key {
+        Escape => 9,
+        Backspace => 22,
+        Tab => 23,
+        Return => 36,
+        ControlLeft => 37,
+        ShiftLeft => 50,
+        ShiftRight => 62,
+        Alt => 64,
+        Space => 65,
+        CapsLock => 66,
+        ScrollLock => 78,
+        ControlRight => 105,
+        PrintScreen => 107,
+        AltGr => 108,
+        Pause => 127,
+        MetaLeft => 133,
+        MetaRight => 134,
+        Unknown(code) => code,
+        _ => return None,
+    }))
+}
+
+/// Returns the X11 keycode of keys in the typing area, whose text depends on the layout.
+///
+/// Other keys (modifiers, arrows, function keys, ...) keep their fixed labels.
+fn text_keycode(key: Key) -> Option<u32> {
+    use Key::*;
+    Some(match key {
+        BackQuote => 49,
+        Num1 => 10,
+        Num2 => 11,
+        Num3 => 12,
+        Num4 => 13,
+        Num5 => 14,
+        Num6 => 15,
+        Num7 => 16,
+        Num8 => 17,
+        Num9 => 18,
+        Num0 => 19,
+        Minus => 20,
+        Equal => 21,
+        KeyQ => 24,
+        KeyW => 25,
+        KeyE => 26,
+        KeyR => 27,
+        KeyT => 28,
+        KeyY => 29,
+        KeyU => 30,
+        KeyI => 31,
+        KeyO => 32,
+        KeyP => 33,
+        LeftBracket => 34,
+        RightBracket => 35,
+        KeyA => 38,
+        KeyS => 39,
+        KeyD => 40,
+        KeyF => 41,
+        KeyG => 42,
+        KeyH => 43,
+        KeyJ => 44,
+        KeyK => 45,
+        KeyL => 46,
+        SemiColon => 47,
+        Quote => 48,
+        BackSlash => 51,
+        IntlBackslash => 94,
+        KeyZ => 52,
+        KeyX => 53,
+        KeyC => 54,
+        KeyV => 55,
+        KeyB => 56,
+        KeyN => 57,
+        KeyM => 58,
+        Comma => 59,
+        Dot => 60,
+        Slash => 61,
+        _ => return None,
+    })
+}
@@ -1,32 +1,57 @@
-#[cfg(target_os = "macos")]
-mod platform {
-    use super::*;
-    use crate::macos_keyboard::resolve_macos_key;
+use crate::input::input::{record_cursor, report_listener_failure, InputEvent, KeyId};
+use crate::input::keyboard::resolve_physical_key;
+use crate::input::layout::KeyboardLayout;
+use crate::platform::macos::accessibility::ensure_accessibility;
+use rdev::{listen, EventType};
+use std::{sync::mpsc::Sender, thread};
 
-    pub fn start_input_listener(tx: Sender<InputEvent>) {
-        thread::spawn(move || {
-            if let Err(err) = listen(move |event| match event.event_type {
-                EventType::KeyPress(key) => {
-                    let raw = format!("{:?}", key);
-                    println!("[INPUT] rdev key: {}", raw);
-                    let label = resolve_macos_key(&raw).unwrap_or_else(|| raw.clone());
-                    tx.send(InputEvent::KeyPress(label)).ok();
-                }
-                EventType::KeyRelease(key) => {
-                    let raw = format!("{:?}", key);
-                    println!("[RELEASE] rdev key: {}", raw);
-                }
-                EventType::ButtonPress(button) => {
-                    let label = format!("Mouse{:?}", button);
-                    tx.send(InputEvent::MouseClick(label)).ok();
-                }
-                _ => {}
-            }) {
-                eprintln!("Failed to listen to keyboard events: {:?}", err);
-            }
-        });
+/// Starts the macOS input event listener in a background thread.
+///
+/// Keys are labelled by their physical (US) position; layouts and Compose sequences aren't
+/// resolved on macOS yet.
+///
+/// # Arguments
+/// * `tx` - A channel `Sender` to push `InputEvent` messages to the application.
+pub fn start_input_listener(tx: Sender<InputEvent>, _layout: KeyboardLayout, _compose: bool) {
+    // Without the permission rdev::listen runs but never sees a key, so check first
+    if !ensure_accessibility() {
+        report_listener_failure(
+            "Input capture needs Accessibility permission. Allow FerrisKeys in System \
+             Settings → Privacy & Security → Accessibility, then restart it."
+                .to_string(),
+        );
+        return;
     }
-}
 
-#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
-pub use platform::start_input_listener;
\ No newline at end of file
+    thread::spawn(move || {
+        if let Err(err) = listen(move |event| match event.event_type {
+            EventType::KeyPress(key) => {
+                debug!("rdev key press: {:?}", key);
+                tx.send(InputEvent::KeyPress(
+                    KeyId::of(key),
+                    resolve_physical_key(key),
+                ))
+                .ok();
+            }
+            EventType::KeyRelease(key) => {
+                debug!("rdev key release: {:?}", key);
+                tx.send(InputEvent::KeyRelease(
+                    KeyId::of(key),
+                    resolve_physical_key(key),
+                ))
+                .ok();
+            }
+            EventType::ButtonPress(button) => {
+                let label = format!("Mouse{:?}", button);
+                tx.send(InputEvent::MouseClick(label)).ok();
+            }
+            EventType::MouseMove { x, y } => record_cursor(x, y),
+            _ => {}
+        }) {
+            report_listener_failure(format!(
+                "Input capture failed ({err:?}). Grant FerrisKeys permission in System \
+                 Settings → Privacy & Security → Accessibility, then restart it."
+            ));
+        }
+    });
+}
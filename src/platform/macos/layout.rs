@@ -0,0 +1,15 @@
+use crate::error::FerrisKeysError;
+use crate::input::layout::KeyboardLayout;
+
+/// Detects the active keyboard layout on macOS.
+///
+/// Not implemented yet: keys are labelled by their physical (US) position, so set `layout`
+/// in the config to pick one.
+///
+/// # Errors
+/// Always returns `FerrisKeysError::LayoutDetection`.
+pub fn detect_layout() -> Result<KeyboardLayout, FerrisKeysError> {
+    Err(FerrisKeysError::LayoutDetection(
+        "not supported on macOS yet".into(),
+    ))
+}
@@ -0,0 +1,3 @@
+pub mod accessibility;
+pub mod input;
+pub mod layout;
@@ -0,0 +1,59 @@
+use raw_window_handle::RawWindowHandle;
+use std::mem::size_of;
+use std::ptr;
+use winapi::shared::windef::HWND;
+use winapi::shared::winerror::SUCCEEDED;
+use winapi::um::dwmapi::{
+    DwmEnableBlurBehindWindow, DwmSetWindowAttribute, DWM_BB_ENABLE, DWM_BLURBEHIND,
+};
+
+/// `DWMWA_SYSTEMBACKDROP_TYPE`, the Windows 11 (22H2+) backdrop attribute, which `winapi`
+/// predates.
+const DWMWA_SYSTEMBACKDROP_TYPE: u32 = 38;
+
+/// `DWMSBT_NONE`, no system backdrop.
+const DWMSBT_NONE: u32 = 1;
+
+/// `DWMSBT_TRANSIENTWINDOW`, the blurred acrylic backdrop used by menus and flyouts.
+const DWMSBT_TRANSIENTWINDOW: u32 = 3;
+
+/// Asks DWM to blur behind a window, or to stop when `enabled` is false.
+///
+/// Internally uses the Windows API:
+/// - `DwmSetWindowAttribute()` with the acrylic system backdrop on Windows 11.
+/// - `DwmEnableBlurBehindWindow()` on older versions, where it blurs on Windows 7 and only
+///   keeps the window transparent on Windows 8 and 10.
+///
+/// # Returns
+/// `false` if the window isn't a Win32 window or DWM refuses both requests.
+pub fn set_blur(window: RawWindowHandle, enabled: bool) -> bool {
+    let RawWindowHandle::Win32(handle) = window else {
+        return false;
+    };
+    let hwnd = handle.hwnd.get() as HWND;
+
+    unsafe {
+        let backdrop = if enabled {
+            DWMSBT_TRANSIENTWINDOW
+        } else {
+            DWMSBT_NONE
+        };
+        let result = DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_SYSTEMBACKDROP_TYPE,
+            &backdrop as *const u32 as *const _,
+            size_of::<u32>() as u32,
+        );
+        if SUCCEEDED(result) {
+            return true;
+        }
+
+        let blur = DWM_BLURBEHIND {
+            dwFlags: DWM_BB_ENABLE,
+            fEnable: enabled.into(),
+            hRgnBlur: ptr::null_mut(),
+            fTransitionOnMaximized: 0,
+        };
+        SUCCEEDED(DwmEnableBlurBehindWindow(hwnd, &blur))
+    }
+}
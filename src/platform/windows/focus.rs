@@ -0,0 +1,28 @@
+use crate::input::focus::FocusedWindow;
+use winapi::um::winuser::{GetClassNameW, GetForegroundWindow, GetWindowTextW};
+
+/// Returns the foreground window's class and title on Windows.
+///
+/// Internally uses the Windows API:
+/// - `GetForegroundWindow()` to get the window the user is working in.
+/// - `GetClassNameW()` and `GetWindowTextW()` to read its class name and title.
+///
+/// # Returns
+/// `None` if no window is in the foreground (e.g. while focus is changing).
+pub fn focused_window() -> Option<FocusedWindow> {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+
+        let mut buf = [0u16; 512];
+        let len = GetClassNameW(hwnd, buf.as_mut_ptr(), buf.len() as i32);
+        let class = String::from_utf16_lossy(&buf[..len.max(0) as usize]);
+
+        let len = GetWindowTextW(hwnd, buf.as_mut_ptr(), buf.len() as i32);
+        let title = String::from_utf16_lossy(&buf[..len.max(0) as usize]);
+
+        Some(FocusedWindow { class, title })
+    }
+}
@@ -1,6 +1,7 @@
-use crate::input::input::InputEvent;
-use crate::input::{keyboard::resolve_physical_key, keymap::resolve_key_label};
-use crate::platform::windows::layout::detect_layout;
+use crate::input::input::{record_cursor, report_listener_failure, InputEvent, KeyId};
+use crate::input::keyboard::{resolve_physical_key, typed_label};
+use crate::input::keymap::resolve_key_label;
+use crate::input::layout::KeyboardLayout;
 use crate::platform::windows::windows::{translate_key_win32, vk_code_from_key};
 use rdev::{listen, EventType, Key};
 use std::{
@@ -10,25 +11,35 @@ use std::{
         Arc,
     },
     thread,
+    time::{Duration, Instant},
 };
 
 /// Starts the input event listener loop for Windows, running in a background thread.
 ///
 /// - Listens for key presses, key releases, and mouse button clicks.
 /// - Uses the current keyboard layout to determine the correct symbol for a key.
-/// - Handles Shift key detection manually to provide shifted character output.
-/// - Translates virtual key codes into localized Unicode characters if Shift is active.
+/// - Handles Shift and AltGr detection manually to provide shifted and third-level output.
+/// - Translates virtual key codes into localized Unicode characters for all printable keys,
+///   falling back to the physical key map when translation yields nothing printable
+///   (e.g. the control character Ctrl+C types).
 ///
 /// All events are sent via the provided `Sender<InputEvent>` to the main thread.
 ///
 /// # Arguments
 /// * `tx` - A `Sender<InputEvent>` used to transmit input events to the UI or processor.
-pub fn start_input_listener(tx: Sender<InputEvent>) {
-    let layout = detect_layout(); // Detect current keyboard layout once at startup
-
+/// * `layout` - Keyboard layout used to resolve shifted symbols.
+/// * `_compose` - Compose sequences are only combined on Linux.
+pub fn start_input_listener(tx: Sender<InputEvent>, layout: KeyboardLayout, _compose: bool) {
     let shift_down = Arc::new(AtomicBool::new(false)); // Track Shift key state
     let shift_flag = shift_down.clone(); // Clone for use in the event handler closure
 
+    let altgr_down = Arc::new(AtomicBool::new(false)); // Track AltGr (right Alt) state
+    let altgr_flag = altgr_down.clone(); // Clone for use in the event handler closure
+
+    let mouse_down = Arc::new(AtomicBool::new(false)); // Track a held mouse button
+    let mouse_flag = mouse_down.clone(); // Clone for use in the event handler closure
+    let mut last_drag: Option<Instant> = None; // Throttles drag events while the mouse moves
+
     // Spawn the listener in its own thread so it doesn't block the main loop
     thread::spawn(move || {
         // Begin listening for input events
@@ -38,42 +49,91 @@ pub fn start_input_listener(tx: Sender<InputEvent>) {
                 // Track when Shift is pressed
                 Key::ShiftLeft | Key::ShiftRight => {
                     shift_flag.store(true, Ordering::SeqCst);
-                    tx.send(InputEvent::KeyPress("⇧ shift".into())).ok();
+                    tx.send(InputEvent::KeyPress(KeyId::of(key), "⇧ shift".into()))
+                        .ok();
+                }
+
+                // Track when AltGr is pressed so third-level symbols resolve
+                Key::AltGr => {
+                    altgr_flag.store(true, Ordering::SeqCst);
+                    tx.send(InputEvent::KeyPress(
+                        KeyId::of(key),
+                        resolve_physical_key(key),
+                    ))
+                    .ok();
                 }
 
                 // Other key presses
                 _ => {
-                    let label = if shift_flag.load(Ordering::SeqCst) {
-                        // If Shift is active, try to resolve the actual shifted symbol
-                        vk_code_from_key(key)
-                            .and_then(|vk| translate_key_win32(vk)) // Try Win32 translation
-                            .unwrap_or_else(|| resolve_key_label(key, &layout)) // Fallback to layout map
-                    } else {
+                    // Try Win32 translation first; it reads the full keyboard state
+                    // (Shift, Caps Lock) so the output matches the active layout. Control
+                    // characters (e.g. Ctrl+C) are rejected so shortcuts show the key.
+                    let translated = vk_code_from_key(key)
+                        .and_then(|vk| translate_key_win32(vk, altgr_flag.load(Ordering::SeqCst)))
+                        .and_then(|text| typed_label(&text));
+
+                    let label = match translated {
+                        Some(text) => text,
+                        // If Shift is active, fall back to the layout map
+                        None if shift_flag.load(Ordering::SeqCst) => {
+                            resolve_key_label(key, &layout)
+                        }
                         // Without Shift, resolve via physical map
-                        resolve_physical_key(key)
+                        None => resolve_physical_key(key),
                     };
 
-                    tx.send(InputEvent::KeyPress(label)).ok();
+                    tx.send(InputEvent::KeyPress(KeyId::of(key), label)).ok();
                 }
             },
 
             // Handle key release events
             EventType::KeyRelease(key) => {
-                if key == Key::ShiftLeft || key == Key::ShiftRight {
-                    shift_flag.store(false, Ordering::SeqCst);
+                match key {
+                    Key::ShiftLeft | Key::ShiftRight => shift_flag.store(false, Ordering::SeqCst),
+                    Key::AltGr => altgr_flag.store(false, Ordering::SeqCst),
+                    _ => {}
                 }
+
+                // Releases use the physical label; they only need to identify the key
+                tx.send(InputEvent::KeyRelease(
+                    KeyId::of(key),
+                    resolve_physical_key(key),
+                ))
+                .ok();
             }
 
             // Handle mouse button presses
             EventType::ButtonPress(button) => {
+                mouse_flag.store(true, Ordering::SeqCst);
                 let label = format!("Mouse{:?}", button);
                 tx.send(InputEvent::MouseClick(label)).ok();
             }
 
+            // Handle mouse button releases (ends any drag)
+            EventType::ButtonRelease(_) => {
+                mouse_flag.store(false, Ordering::SeqCst);
+                last_drag = None;
+            }
+
+            EventType::MouseMove { x, y } => {
+                record_cursor(x, y);
+
+                // Moving with a button held is a drag; re-send periodically so it stays visible
+                if mouse_flag.load(Ordering::SeqCst)
+                    && last_drag.is_none_or(|t| t.elapsed() >= Duration::from_millis(100))
+                {
+                    last_drag = Some(Instant::now());
+                    tx.send(InputEvent::MouseClick("󰍽 drag".into())).ok();
+                }
+            }
+
             // Ignore other events
             _ => {}
         }) {
-            eprintln!("Failed to listen to keyboard events: {:?}", err);
+            report_listener_failure(format!(
+                "Input capture failed ({err:?}). Restart FerrisKeys; if this keeps happening, \
+                 check that security software isn't blocking keyboard hooks."
+            ));
         }
     });
 }
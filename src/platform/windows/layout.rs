@@ -1,3 +1,4 @@
+use crate::error::FerrisKeysError;
 use crate::input::layout::KeyboardLayout;
 use winapi::shared::minwindef::HKL;
 use winapi::um::processthreadsapi::GetCurrentThreadId;
@@ -12,27 +13,45 @@ use winapi::um::winuser::GetKeyboardLayout;
 /// The returned `HKL` contains the language ID in the high word. Known mappings:
 /// - `0x0809` → `KeyboardLayout::UnitedKingdom`
 /// - `0x0409` → `KeyboardLayout::UnitedStates`
+/// - `0x040A`, `0x0C0A` → `KeyboardLayout::Spain`
+/// - `0x0410` → `KeyboardLayout::Italy`
+/// - `0x041D`, `0x040B` → `KeyboardLayout::Sweden` (Finnish shares the Swedish layout)
+/// - `0x0414`, `0x0814` → `KeyboardLayout::Norway`
+/// - `0x0406` → `KeyboardLayout::Denmark`
 ///
 /// Other layout IDs are returned as `KeyboardLayout::Other(layout_id)`.
 ///
 /// # Returns
 /// A `KeyboardLayout` enum variant corresponding to the current layout.
-pub fn detect_layout() -> KeyboardLayout {
+///
+/// # Errors
+/// Returns `FerrisKeysError::LayoutDetection` if the thread has no keyboard layout.
+pub fn detect_layout() -> Result<KeyboardLayout, FerrisKeysError> {
     unsafe {
         // Get the current thread ID
         let thread_id = GetCurrentThreadId();
 
         // Get the keyboard layout (HKL) for that thread
         let hkl: HKL = GetKeyboardLayout(thread_id);
+        if hkl.is_null() {
+            return Err(FerrisKeysError::LayoutDetection(
+                "GetKeyboardLayout returned no layout".into(),
+            ));
+        }
 
         // Extract the high word (bits 16–31), which represents the layout ID
         let layout_id = ((hkl as usize >> 16) & 0xFFFF) as u16;
 
         // Match known layouts or return a generic fallback
-        match layout_id {
-            0x0809 => KeyboardLayout::UnitedKingdom, // English (UK)
-            0x0409 => KeyboardLayout::UnitedStates,  // English (US)
-            _ => KeyboardLayout::Other(layout_id),   // Other/unknown layout
-        }
+        Ok(match layout_id {
+            0x0809 => KeyboardLayout::UnitedKingdom,   // English (UK)
+            0x0409 => KeyboardLayout::UnitedStates,    // English (US)
+            0x040A | 0x0C0A => KeyboardLayout::Spain,  // Spanish (traditional and modern sort)
+            0x0410 => KeyboardLayout::Italy,           // Italian
+            0x041D | 0x040B => KeyboardLayout::Sweden, // Swedish, Finnish
+            0x0414 | 0x0814 => KeyboardLayout::Norway, // Norwegian (Bokmål, Nynorsk)
+            0x0406 => KeyboardLayout::Denmark,         // Danish
+            _ => KeyboardLayout::Other(layout_id),     // Other/unknown layout
+        })
     }
 }
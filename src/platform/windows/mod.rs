@@ -1,4 +1,8 @@
+pub mod blur;
+pub mod focus;
 pub mod input;
 pub mod layout;
+pub mod monitor;
+#[cfg(feature = "windows-tray")]
 pub mod tray;
 pub mod windows;
@@ -0,0 +1,54 @@
+use crate::ui::monitor::MonitorRect;
+use std::ptr;
+use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+use winapi::shared::windef::{HDC, HMONITOR, LPRECT};
+use winapi::um::winuser::{EnumDisplayMonitors, GetMonitorInfoW, MONITORINFO};
+
+/// Enumerates connected monitors on Windows using `EnumDisplayMonitors`.
+///
+/// Each monitor's bounds and work area (excluding the taskbar) are read with
+/// `GetMonitorInfoW`, so coordinates are in virtual-desktop pixels (the primary monitor
+/// starts at `0, 0`).
+///
+/// # Returns
+/// A list of monitors in enumeration order, or an empty list if enumeration fails.
+pub fn detect_monitors() -> Vec<MonitorRect> {
+    let mut monitors: Vec<MonitorRect> = Vec::new();
+
+    unsafe {
+        EnumDisplayMonitors(
+            ptr::null_mut(),
+            ptr::null(),
+            Some(collect_monitor),
+            &mut monitors as *mut Vec<MonitorRect> as LPARAM,
+        );
+    }
+
+    monitors
+}
+
+/// Callback for `EnumDisplayMonitors` that pushes each monitor into the `Vec` passed via `data`.
+unsafe extern "system" fn collect_monitor(
+    monitor: HMONITOR,
+    _hdc: HDC,
+    _rect: LPRECT,
+    data: LPARAM,
+) -> BOOL {
+    let monitors = &mut *(data as *mut Vec<MonitorRect>);
+
+    let mut info: MONITORINFO = std::mem::zeroed();
+    info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+
+    if GetMonitorInfoW(monitor, &mut info) != 0 {
+        let r = info.rcMonitor;
+        let w = info.rcWork; // Excludes the taskbar
+        monitors.push(MonitorRect {
+            position: [r.left as f32, r.top as f32],
+            size: [(r.right - r.left) as f32, (r.bottom - r.top) as f32],
+            work_position: [w.left as f32, w.top as f32],
+            work_size: [(w.right - w.left) as f32, (w.bottom - w.top) as f32],
+        });
+    }
+
+    TRUE
+}
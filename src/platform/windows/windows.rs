@@ -2,7 +2,13 @@ use rdev::Key;
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
 use winapi::shared::minwindef::HKL;
-use winapi::um::winuser::{GetKeyboardLayout, GetKeyboardState, MapVirtualKeyW, ToUnicodeEx};
+use winapi::um::winuser::{
+    GetKeyboardLayout, GetKeyboardState, MapVirtualKeyW, ToUnicodeEx, VK_CONTROL, VK_LCONTROL,
+    VK_MENU, VK_RMENU,
+};
+
+/// `ToUnicodeEx` flag (bit 2) that translates without changing the keyboard state.
+const TRANSLATE_KEEP_STATE: u32 = 0x4;
 
 /// Translates a given Windows virtual key code into its corresponding Unicode character(s),
 /// considering the current keyboard layout and key state (e.g., Shift pressed).
@@ -13,13 +19,19 @@ use winapi::um::winuser::{GetKeyboardLayout, GetKeyboardState, MapVirtualKeyW, T
 /// - `MapVirtualKeyW` to convert the virtual key to a scan code.
 /// - `ToUnicodeEx` to produce the Unicode output using the above state.
 ///
+/// AltGr is reported to `ToUnicodeEx` as Ctrl+Alt, which is how Windows encodes it, so
+/// layouts with a third level (e.g. AltGr+E → €) resolve correctly. The translation
+/// passes `TRANSLATE_KEEP_STATE`, so it never consumes a dead key (accent) the focused
+/// application is about to combine with the next key; the accent is returned as the label.
+///
 /// # Arguments
 /// * `vk_code` - The virtual key code to translate (e.g., 0x41 for 'A').
+/// * `altgr` - Whether AltGr (right Alt) is currently held.
 ///
 /// # Returns
 /// * `Some(String)` - If the virtual key translates into one or more Unicode characters.
 /// * `None` - If translation fails or results in no output.
-pub fn translate_key_win32(vk_code: u32) -> Option<String> {
+pub fn translate_key_win32(vk_code: u32, altgr: bool) -> Option<String> {
     unsafe {
         let layout: HKL = GetKeyboardLayout(0);
 
@@ -29,6 +41,13 @@ pub fn translate_key_win32(vk_code: u32) -> Option<String> {
             return None;
         }
 
+        // AltGr is seen by the layout as Ctrl+Alt held together
+        if altgr {
+            for vk in [VK_CONTROL, VK_LCONTROL, VK_MENU, VK_RMENU] {
+                key_state[vk as usize] |= 0x80;
+            }
+        }
+
         // Convert VK to scan code
         let scan_code = MapVirtualKeyW(vk_code, 0);
 
@@ -40,10 +59,19 @@ pub fn translate_key_win32(vk_code: u32) -> Option<String> {
             key_state.as_ptr(),
             buffer.as_mut_ptr(),
             buffer.len() as i32,
-            0,
+            TRANSLATE_KEEP_STATE,
             layout,
         );
 
+        // A negative result means a dead key; its accent is in the buffer
+        if result < 0 {
+            return Some(
+                OsString::from_wide(&buffer[..1])
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+        }
+
         // Convert result to String if characters were returned
         if result > 0 {
             Some(
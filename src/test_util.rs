@@ -0,0 +1,46 @@
+//! Helpers shared by the unit tests.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Numbers the temp files handed out, so tests running in parallel never share one.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A unique path in the temp dir. Its file is removed on drop, even if the test panics.
+pub struct TempPath(PathBuf);
+
+impl TempPath {
+    /// Returns a fresh path with the given extension, e.g. `ferriskeys-1234-0.toml`.
+    /// Nothing is created.
+    pub fn new(extension: impl AsRef<OsStr>) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut path = std::env::temp_dir().join(format!("ferriskeys-{}-{id}", std::process::id()));
+        path.set_extension(extension);
+        Self(path)
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Writes `contents` to a temporary config file and runs `test` with its path. The file
+/// is removed afterwards, even if `test` panics.
+pub fn with_temp_config(contents: &str, test: impl FnOnce(&Path)) {
+    let path = TempPath::new("toml");
+    fs::write(&*path, contents).unwrap();
+    test(&path);
+}
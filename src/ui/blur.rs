@@ -0,0 +1,37 @@
+//! Compositor blur behind the overlay, for `blur_background`.
+
+use raw_window_handle::HasWindowHandle;
+
+/// Asks the compositor to blur what is behind `window` (or to stop, when `enabled` is
+/// false), so keys stay legible over busy screens. Where blur isn't supported this does
+/// nothing but log it.
+///
+/// On Linux only X11 windows (including XWayland) can be blurred; see `set_blur`.
+pub fn blur_behind(window: &impl HasWindowHandle, enabled: bool) {
+    let Ok(handle) = window.window_handle() else {
+        warn!("Could not get the overlay window to change its background blur.");
+        return;
+    };
+
+    if set_blur(handle.as_raw(), enabled) {
+        match enabled {
+            true => info!("Requested background blur from the compositor"),
+            false => info!("Turned off background blur"),
+        }
+    } else if enabled {
+        warn!("Background blur is not supported here. Ignoring blur_background.");
+    }
+}
+
+// Platform-specific blur requests
+#[cfg(target_os = "windows")]
+use crate::platform::windows::blur::set_blur;
+
+#[cfg(target_os = "linux")]
+use crate::platform::linux::blur::set_blur;
+
+/// Background blur is not implemented on macOS yet.
+#[cfg(target_os = "macos")]
+fn set_blur(_window: raw_window_handle::RawWindowHandle, _enabled: bool) -> bool {
+    false
+}
@@ -0,0 +1,146 @@
+use crate::config::config::Config;
+use crate::core::buffer::{format_label, typed_text};
+use crate::input::input::KeyId;
+use crate::input::keymap::KeyCategory;
+use crate::ui::layout::content_rect;
+use crate::ui::ui::paint_text;
+
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, emath::GuiRounding, FontId};
+
+/// Most pieces (characters or inline keys) kept; older ones scroll off the left.
+const CAPTION_LIMIT: usize = 200;
+
+/// The typed text shown by `display_mode = "caption"`, like live subtitles.
+///
+/// Text is kept as pieces so Backspace removes a whole inline key such as `<Tab>`,
+/// not just its last character.
+pub struct Caption {
+    pub pieces: Vec<String>,   // Typed characters and inline keys, oldest first
+    pub time: Option<Instant>, // When the caption last changed (for clearing it)
+}
+
+impl Caption {
+    /// Creates an empty caption.
+    pub fn new() -> Self {
+        Self {
+            pieces: Vec::new(),
+            time: None,
+        }
+    }
+
+    /// Returns `true` if there is no text to show.
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// Removes all text at once.
+    pub fn clear(&mut self) {
+        self.pieces.clear();
+    }
+
+    /// Returns the caption as one string.
+    pub fn text(&self) -> String {
+        self.pieces.concat()
+    }
+
+    /// Adds a key press to the caption.
+    ///
+    /// - Letters, digits, symbols and Space append the text they type.
+    /// - Backspace removes the last piece.
+    /// - Shift is left out, since it only changes the typed character.
+    /// - Other keys appear inline as their glyph (e.g. `⌃`, `↑`), or as `<name>`.
+    pub fn push_key(&mut self, id: &KeyId, label: &str, config: &Config) {
+        match id.0.as_str() {
+            "Backspace" => {
+                self.pieces.pop();
+            }
+            "ShiftLeft" | "ShiftRight" => return,
+            _ => {
+                let (icon, label) = format_label(label, false, config);
+                let piece = match typed_text(&icon, &label) {
+                    Some(text) => text,
+                    None if !icon.is_empty() => icon,
+                    None if label.chars().count() == 1 => label,
+                    None => format!("<{label}>"),
+                };
+                self.pieces.push(piece);
+            }
+        }
+
+        let excess = self.pieces.len().saturating_sub(CAPTION_LIMIT);
+        self.pieces.drain(..excess);
+        self.time = Some(Instant::now());
+    }
+
+    /// Clears the caption once nothing was typed for `timeout`.
+    pub fn expire(&mut self, now: Instant, timeout: Duration) {
+        if self.time.is_some_and(|t| now.duration_since(t) >= timeout) {
+            self.clear();
+            self.time = None;
+        }
+    }
+
+    /// Draws the caption bar along the bottom of the overlay in the `Normal` style.
+    ///
+    /// The text is right-aligned so the newest input stays in view; older text is
+    /// clipped on the left. The caption clears after `timeout_ms` without input.
+    pub fn render(&mut self, ui: &mut egui::Ui, config: &Config, max_width: f32) {
+        self.expire(Instant::now(), Duration::from_millis(config.timeout_ms));
+        if self.is_empty() {
+            return;
+        }
+
+        let style = config
+            .styles
+            .get(&KeyCategory::Normal)
+            .cloned()
+            .unwrap_or_else(Config::fallback_style);
+        let content = content_rect(config, ui.max_rect(), max_width);
+        let mut bar = egui::Rect::from_min_max(
+            egui::pos2(content.left(), content.bottom() - style.height),
+            content.right_bottom(),
+        );
+        if config.pixel_snap {
+            bar = bar.round_to_pixels(ui.pixels_per_point());
+        }
+
+        let painter = ui.painter_at(bar);
+        painter.rect_filled(bar, egui::CornerRadius::same(8), style.bg_color);
+        paint_text(
+            &painter,
+            &style,
+            egui::pos2(bar.right() - style.text_size / 2.0, bar.center().y),
+            egui::Align2::RIGHT_CENTER,
+            &self.text(),
+            FontId::proportional(style.text_size),
+            config.pixel_snap,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(caption: &mut Caption, config: &Config, id: &str, label: &str) {
+        caption.push_key(&KeyId(id.into()), label, config);
+    }
+
+    #[test]
+    fn backspace_removes_the_last_piece() {
+        let config = Config::default();
+        let mut caption = Caption::new();
+        press(&mut caption, &config, "KeyH", "h");
+        press(&mut caption, &config, "KeyI", "i");
+        press(&mut caption, &config, "Space", "Space");
+        press(&mut caption, &config, "Tab", "Tab");
+        assert_eq!(caption.text(), "hi <Tab>");
+
+        press(&mut caption, &config, "Backspace", "Backspace");
+        press(&mut caption, &config, "ShiftLeft", "ShiftLeft");
+        press(&mut caption, &config, "Num1", "!");
+        assert_eq!(caption.text(), "hi !");
+    }
+}
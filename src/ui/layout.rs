@@ -0,0 +1,276 @@
+//! Where keys go on screen: packing the row into the overlay, without painting anything.
+
+use crate::config::config::{Config, Overflow, RowAlign};
+use crate::core::buffer::{entry_style, KeyBuffer, KeyEntry, KEY_PADDING};
+
+use eframe::egui;
+
+impl KeyBuffer {
+    /// Computes the on-screen box for each key that fits, without painting anything.
+    ///
+    /// Keys are packed right to left from the right edge of `panel`, newest first, until
+    /// the next one would exceed `max_width`. The row, as tall as its tallest box, is
+    /// centered vertically in `panel`, and shorter boxes are placed in it per `row_align`.
+    /// The result is in display order (oldest on the left), and each rect is the full,
+    /// unanimated key box.
+    ///
+    /// With `overflow = "scroll"`, packing doesn't stop at `max_width`: the row is shifted
+    /// right by the current `scroll`, and keys entirely left of `max_width` are left out.
+    ///
+    /// # Returns
+    /// The visible keys paired with their boxes. In trim mode, keys not in the list should
+    /// be trimmed.
+    pub fn layout(
+        &self,
+        config: &Config,
+        panel: egui::Rect,
+        max_width: f32,
+    ) -> Vec<(KeyEntry, egui::Rect)> {
+        let scrolling = config.overflow == Overflow::Scroll;
+        let visible_left = panel.right() - max_width;
+        let mut x = panel.right() + if scrolling { self.scroll } else { 0.0 };
+        let mut fitted = vec![];
+
+        // Determine which keys can fit on the screen from right to left
+        for key in self.keys.iter().rev() {
+            let style = entry_style(config, key);
+            let width = style.width + KEY_PADDING;
+
+            if scrolling {
+                // Everything further left is scrolled out of view
+                if x - KEY_PADDING <= visible_left {
+                    break;
+                }
+            } else if panel.right() - x + width > max_width {
+                break;
+            }
+
+            x -= width;
+            fitted.push((key.clone(), style, x));
+        }
+
+        // Lay out from left to right (restore original order)
+        fitted.reverse();
+        let row_height = fitted
+            .iter()
+            .map(|(_, style, _)| style.height)
+            .fold(0.0, f32::max);
+
+        fitted
+            .into_iter()
+            .map(|(key, style, x)| {
+                let rect = egui::Rect::from_min_size(
+                    egui::pos2(
+                        x,
+                        row_top(config.row_align, panel, row_height, style.height),
+                    ),
+                    egui::vec2(style.width, style.height),
+                );
+                (key, rect)
+            })
+            .collect()
+    }
+}
+
+/// Returns the area keys are drawn in: the rightmost `max_width` of `panel`, shrunk to
+/// `max_content_width` / `max_content_height` (when set) around its right-center.
+pub fn content_rect(config: &Config, panel: egui::Rect, max_width: f32) -> egui::Rect {
+    let cap = |value: f32, limit: f32| if limit > 0.0 { value.min(limit) } else { value };
+    let width = cap(max_width, config.max_content_width);
+    let height = cap(panel.height(), config.max_content_height);
+
+    egui::Rect::from_min_max(
+        egui::pos2(panel.right() - width, panel.center().y - height / 2.0),
+        egui::pos2(panel.right(), panel.center().y + height / 2.0),
+    )
+}
+
+/// Returns the top of a `height` tall box in a `row_height` tall row centered in `panel`.
+pub fn row_top(align: RowAlign, panel: egui::Rect, row_height: f32, height: f32) -> f32 {
+    let top = panel.center().y - row_height / 2.0;
+    match align {
+        RowAlign::Top => top,
+        RowAlign::Center => top + (row_height - height) / 2.0,
+        RowAlign::Bottom => top + row_height - height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::buffer::tests::buffer;
+
+    /// Builds a 90px tall panel spanning `0..right`.
+    fn panel(right: f32) -> egui::Rect {
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(right, 90.0))
+    }
+
+    /// Summarises a layout as `(label, left, right)` triples.
+    fn summary(layout: &[(KeyEntry, egui::Rect)]) -> Vec<(String, f32, f32)> {
+        layout
+            .iter()
+            .map(|(key, rect)| (key.label.clone(), rect.left(), rect.right()))
+            .collect()
+    }
+
+    // Default `Normal` keys are 90 wide, plus 8 padding = 98 per key.
+
+    #[test]
+    fn exact_fit_keeps_every_key() {
+        let config = Config::default();
+        let layout = buffer(&["A", "B", "C"]).layout(&config, panel(294.0), 294.0);
+
+        assert_eq!(
+            summary(&layout),
+            vec![
+                ("A".to_string(), 0.0, 90.0),
+                ("B".to_string(), 98.0, 188.0),
+                ("C".to_string(), 196.0, 286.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow_keeps_newest_keys_right_aligned() {
+        let config = Config::default();
+        let layout = buffer(&["A", "B", "C", "D", "E"]).layout(&config, panel(500.0), 294.0);
+
+        assert_eq!(
+            summary(&layout),
+            vec![
+                ("C".to_string(), 206.0, 296.0),
+                ("D".to_string(), 304.0, 394.0),
+                ("E".to_string(), 402.0, 492.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow_by_one_pixel_drops_oldest_key() {
+        let config = Config::default();
+        let layout = buffer(&["A", "B", "C"]).layout(&config, panel(293.0), 293.0);
+
+        assert_eq!(
+            summary(&layout),
+            vec![
+                ("B".to_string(), 97.0, 187.0),
+                ("C".to_string(), 195.0, 285.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_oversized_key_is_not_laid_out() {
+        // `Space` is 260 wide, so it can never fit in 200
+        let config = Config::default();
+        let layout = buffer(&["space"]).layout(&config, panel(200.0), 200.0);
+
+        assert!(layout.is_empty());
+    }
+
+    #[test]
+    fn oversized_newest_key_hides_older_keys() {
+        let config = Config::default();
+        let layout = buffer(&["A", "space"]).layout(&config, panel(200.0), 200.0);
+
+        assert!(layout.is_empty());
+    }
+
+    #[test]
+    fn boxes_use_category_height() {
+        let config = Config::default();
+        let layout = buffer(&["A"]).layout(&config, panel(98.0), 98.0);
+
+        assert_eq!(layout[0].1.top(), 0.0);
+        assert_eq!(layout[0].1.height(), 90.0);
+    }
+
+    #[test]
+    fn boxes_are_centered_vertically_in_the_panel() {
+        let mut config = Config::default();
+        config
+            .styles
+            .get_mut(&crate::input::keymap::KeyCategory::Normal)
+            .unwrap()
+            .height = 60.0;
+        let tall_panel = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(98.0, 120.0));
+        let layout = buffer(&["A"]).layout(&config, tall_panel, 98.0);
+
+        assert_eq!(layout[0].1.top(), 30.0);
+        assert_eq!(layout[0].1.bottom(), 90.0);
+    }
+
+    #[test]
+    fn mixed_heights_follow_row_align() {
+        let mut config = Config::default();
+        config
+            .styles
+            .get_mut(&crate::input::keymap::KeyCategory::Escape)
+            .unwrap()
+            .height = 70.0;
+        let tops = |config: &Config| {
+            buffer(&["A", "esc"])
+                .layout(config, panel(196.0), 196.0)
+                .iter()
+                .map(|(_, rect)| rect.top())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(tops(&config), [0.0, 20.0]);
+        config.row_align = RowAlign::Top;
+        assert_eq!(tops(&config), [0.0, 0.0]);
+        config.row_align = RowAlign::Center;
+        assert_eq!(tops(&config), [0.0, 10.0]);
+    }
+
+    #[test]
+    fn content_caps_shrink_the_row_area() {
+        let mut config = Config::default();
+        let window = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1000.0, 400.0));
+        assert_eq!(content_rect(&config, window, 1000.0), window);
+
+        config.max_content_width = 300.0;
+        config.max_content_height = 100.0;
+        assert_eq!(
+            content_rect(&config, window, 1000.0),
+            egui::Rect::from_min_max(egui::pos2(700.0, 150.0), egui::pos2(1000.0, 250.0))
+        );
+    }
+
+    #[test]
+    fn scroll_overflow_shifts_the_row_right() {
+        let config = Config {
+            overflow: Overflow::Scroll,
+            ..Config::default()
+        };
+
+        // Half way through the slide, B peeks in on the left and E is partly off the right
+        let mut buffer = buffer(&["A", "B", "C", "D", "E"]);
+        buffer.scroll = 49.0;
+        let layout = buffer.layout(&config, panel(500.0), 294.0);
+        assert_eq!(
+            summary(&layout),
+            vec![
+                ("B".to_string(), 157.0, 247.0),
+                ("C".to_string(), 255.0, 345.0),
+                ("D".to_string(), 353.0, 443.0),
+                ("E".to_string(), 451.0, 541.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn fit_uses_measured_widths() {
+        let mut config = Config::default();
+        config
+            .styles
+            .get_mut(&crate::input::keymap::KeyCategory::Normal)
+            .unwrap()
+            .auto_width = true;
+
+        // Three 32px keys plus padding fit in 120
+        let mut buffer = buffer(&["A", "B", "C"]);
+        buffer.keys.iter_mut().for_each(|k| k.text_width = 12.0);
+        assert_eq!(buffer.layout(&config, panel(120.0), 120.0).len(), 3);
+    }
+}
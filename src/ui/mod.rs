@@ -1,3 +1,13 @@
+pub mod blur;
+pub mod caption;
+pub mod layout;
+pub mod monitor;
+pub mod numpad;
+pub mod screenshot;
+pub mod settings;
+#[cfg(feature = "sound")]
+pub mod sound;
+#[cfg(all(feature = "windows-tray", target_os = "windows"))]
 pub mod tray;
 pub mod ui;
 pub mod visualiser;
@@ -0,0 +1,124 @@
+use crate::config::config::{Anchor, Config, PositionOrigin};
+
+/// Geometry of a single display, in absolute desktop pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorRect {
+    /// Top-left corner of the monitor (x, y).
+    pub position: [f32; 2],
+    /// Size of the monitor (width, height).
+    pub size: [f32; 2],
+    /// Top-left corner of the usable area, excluding taskbars/panels where known.
+    pub work_position: [f32; 2],
+    /// Size of the usable area, excluding taskbars/panels where known.
+    pub work_size: [f32; 2],
+}
+
+impl MonitorRect {
+    /// Returns `true` if the given desktop point lies on this monitor.
+    pub fn contains(&self, point: [f32; 2]) -> bool {
+        point[0] >= self.position[0]
+            && point[1] >= self.position[1]
+            && point[0] < self.position[0] + self.size[0]
+            && point[1] < self.position[1] + self.size[1]
+    }
+
+    /// Computes the window position that places a window of `size` at `anchor`
+    /// within this monitor's work area, `margin` pixels (horizontally, vertically) away
+    /// from the edges.
+    pub fn anchored(&self, anchor: Anchor, size: [f32; 2], margin: [f32; 2]) -> [f32; 2] {
+        let (fx, fy) = anchor.fractions();
+        [
+            self.work_position[0]
+                + fx * (self.work_size[0] - size[0])
+                + margin[0] * (1.0 - 2.0 * fx),
+            self.work_position[1]
+                + fy * (self.work_size[1] - size[1])
+                + margin[1] * (1.0 - 2.0 * fy),
+        ]
+    }
+}
+
+/// Resolves the configured window position to absolute desktop pixels.
+///
+/// - With an `anchor`, the window is placed at that edge/corner of the configured
+///   `monitor` (or the first one), keeping `margin` pixels from the edges.
+/// - With `position_relative_to = "monitor"`, `position` is offset by the top-left corner
+///   of the configured `monitor`.
+///
+/// If the monitor doesn't exist (or detection fails), the position is used as-is.
+pub fn resolve_position(config: &Config) -> [f32; 2] {
+    resolve_position_on(config, &detect_monitors())
+}
+
+/// Resolves the configured window position like [`resolve_position`], then clamps it so
+/// the whole window stays on the monitor it lands on.
+///
+/// Used when the config is hot-reloaded, where monitors may have changed since startup.
+pub fn resolve_clamped_position(config: &Config) -> [f32; 2] {
+    let monitors = detect_monitors();
+    let position = resolve_position_on(config, &monitors);
+    clamp_to_monitor(position, config, &monitors)
+}
+
+/// Clamps a window position so the whole window (of the configured `size`) stays on the
+/// monitor it lands on, or on the configured `monitor` if it lands on none.
+///
+/// With no monitors detected, the position is returned as-is.
+pub fn clamp_to_monitor(position: [f32; 2], config: &Config, monitors: &[MonitorRect]) -> [f32; 2] {
+    let Some(m) = monitors
+        .iter()
+        .find(|m| m.contains(position))
+        .or(monitors.get(config.monitor))
+        .or(monitors.first())
+    else {
+        return position;
+    };
+
+    // Keep the top-left on screen even if the window is larger than the monitor
+    let max_x = (m.position[0] + m.size[0] - config.size[0]).max(m.position[0]);
+    let max_y = (m.position[1] + m.size[1] - config.size[1]).max(m.position[1]);
+    [
+        position[0].clamp(m.position[0], max_x),
+        position[1].clamp(m.position[1], max_y),
+    ]
+}
+
+/// Resolves the configured window position against an already-detected monitor list.
+fn resolve_position_on(config: &Config, monitors: &[MonitorRect]) -> [f32; 2] {
+    if let Some(anchor) = config.anchor {
+        if let Some(m) = monitors.get(config.monitor).or(monitors.first()) {
+            return m.anchored(anchor, config.size, config.margin);
+        }
+    }
+
+    if config.position_relative_to == PositionOrigin::Desktop {
+        return config.position;
+    }
+
+    match monitors.get(config.monitor) {
+        Some(m) => [
+            m.position[0] + config.position[0],
+            m.position[1] + config.position[1],
+        ],
+        None => {
+            warn!(
+                "Monitor {} not found. Using desktop coordinates.",
+                config.monitor
+            );
+            config.position
+        }
+    }
+}
+
+// Platform-specific monitor enumeration
+#[cfg(target_os = "windows")]
+pub use crate::platform::windows::monitor::detect_monitors;
+
+#[cfg(target_os = "linux")]
+pub use crate::platform::linux::monitor::detect_monitors;
+
+/// Monitor enumeration is not implemented on macOS yet.
+#[cfg(target_os = "macos")]
+pub fn detect_monitors() -> Vec<MonitorRect> {
+    Vec::new()
+}
@@ -0,0 +1,171 @@
+use crate::config::config::Config;
+use crate::input::input::KeyId;
+use crate::input::keymap::KeyCategory;
+use crate::ui::ui::paint_text;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, emath::GuiRounding, FontId};
+
+/// Columns and rows of the numpad grid.
+const GRID_COLUMNS: f32 = 4.0;
+const GRID_ROWS: f32 = 5.0;
+
+/// Gap between grid cells, in pixels.
+const CELL_GAP: f32 = 4.0;
+
+/// Each numpad key as `(key id, label, column, row, columns wide, rows tall)`, laid out
+/// like a physical numpad: `+` and Enter are two rows tall, `0` two columns wide.
+const NUMPAD_KEYS: [(&str, &str, f32, f32, f32, f32); 17] = [
+    ("NumLock", "Num", 0.0, 0.0, 1.0, 1.0),
+    ("KpDivide", "/", 1.0, 0.0, 1.0, 1.0),
+    ("KpMultiply", "*", 2.0, 0.0, 1.0, 1.0),
+    ("KpMinus", "-", 3.0, 0.0, 1.0, 1.0),
+    ("Kp7", "7", 0.0, 1.0, 1.0, 1.0),
+    ("Kp8", "8", 1.0, 1.0, 1.0, 1.0),
+    ("Kp9", "9", 2.0, 1.0, 1.0, 1.0),
+    ("KpPlus", "+", 3.0, 1.0, 1.0, 2.0),
+    ("Kp4", "4", 0.0, 2.0, 1.0, 1.0),
+    ("Kp5", "5", 1.0, 2.0, 1.0, 1.0),
+    ("Kp6", "6", 2.0, 2.0, 1.0, 1.0),
+    ("Kp1", "1", 0.0, 3.0, 1.0, 1.0),
+    ("Kp2", "2", 1.0, 3.0, 1.0, 1.0),
+    ("Kp3", "3", 2.0, 3.0, 1.0, 1.0),
+    ("KpReturn", "⏎", 3.0, 3.0, 1.0, 2.0),
+    ("Kp0", "0", 0.0, 4.0, 2.0, 1.0),
+    ("KpDelete", ".", 2.0, 4.0, 1.0, 1.0),
+];
+
+/// A fixed numpad-shaped grid, shown with `show_numpad_grid`, that lights up numpad keys
+/// in place instead of adding them to the scrolling row.
+pub struct NumpadGrid {
+    pub pressed: HashMap<&'static str, Instant>, // When each numpad key was last pressed
+}
+
+impl NumpadGrid {
+    /// Creates a grid with no keys lit.
+    pub fn new() -> Self {
+        Self {
+            pressed: HashMap::new(),
+        }
+    }
+
+    /// Returns `true` if `id` is a key on the numpad grid.
+    pub fn contains(id: &KeyId) -> bool {
+        NUMPAD_KEYS.iter().any(|(key, ..)| *key == id.0)
+    }
+
+    /// Lights up the cell for `id`, if it is a numpad key.
+    pub fn press(&mut self, id: &KeyId) {
+        if let Some((key, ..)) = NUMPAD_KEYS.iter().find(|(key, ..)| *key == id.0) {
+            self.pressed.insert(key, Instant::now());
+        }
+    }
+
+    /// Returns `true` while any cell is still lit.
+    pub fn is_lit(&self) -> bool {
+        !self.pressed.is_empty()
+    }
+
+    /// Unlights every cell at once.
+    pub fn clear(&mut self) {
+        self.pressed.clear();
+    }
+
+    /// Returns the width the grid takes up for a panel `height` pixels tall.
+    pub fn width(height: f32) -> f32 {
+        height / GRID_ROWS * GRID_COLUMNS
+    }
+
+    /// Returns the cell rect for a key at `column`, `row` spanning `columns` x `rows`, in
+    /// a grid filling `grid`.
+    fn cell_rect(
+        grid: egui::Rect,
+        (column, row, columns, rows): (f32, f32, f32, f32),
+    ) -> egui::Rect {
+        let cell = egui::vec2(grid.width() / GRID_COLUMNS, grid.height() / GRID_ROWS);
+        egui::Rect::from_min_size(
+            grid.min + egui::vec2(column * cell.x, row * cell.y),
+            egui::vec2(columns * cell.x, rows * cell.y),
+        )
+        .shrink(CELL_GAP / 2.0)
+    }
+
+    /// Draws the grid along the left edge of the overlay in the `Numeric` style.
+    ///
+    /// Unpressed cells are drawn faintly; a press lights its cell fully, fading back over
+    /// `timeout_ms`.
+    ///
+    /// # Returns
+    /// The width taken up, which the key row should leave free.
+    pub fn render(&mut self, ui: &mut egui::Ui, config: &Config) -> f32 {
+        let timeout = Duration::from_millis(config.timeout_ms);
+        self.pressed.retain(|_, time| time.elapsed() < timeout);
+
+        let style = config
+            .styles
+            .get(&KeyCategory::Numeric)
+            .cloned()
+            .unwrap_or_else(Config::fallback_style);
+        let panel = ui.max_rect();
+        let grid = egui::Rect::from_min_size(
+            panel.left_top(),
+            egui::vec2(Self::width(panel.height()), panel.height()),
+        );
+        let font = FontId::proportional(style.text_size.min(grid.height() / GRID_ROWS * 0.6));
+
+        for (key, label, column, row, columns, rows) in NUMPAD_KEYS {
+            let mut rect = Self::cell_rect(grid, (column, row, columns, rows));
+            if config.pixel_snap {
+                rect = rect.round_to_pixels(ui.pixels_per_point());
+            }
+            let lit = self.pressed.get(key).map_or(0.0, |time| {
+                1.0 - time.elapsed().as_secs_f32() / timeout.as_secs_f32()
+            });
+
+            let mut cell_style = style.clone();
+            cell_style.bg_color = style.bg_color.gamma_multiply(0.25 + 0.75 * lit);
+            cell_style.fg_color = style.fg_color.gamma_multiply(0.4 + 0.6 * lit);
+
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, egui::CornerRadius::same(6), cell_style.bg_color);
+            paint_text(
+                &painter,
+                &cell_style,
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                label,
+                font.clone(),
+                config.pixel_snap,
+            );
+        }
+
+        grid.width() + CELL_GAP
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_and_enter_span_two_cells() {
+        // 4x5 cells of 20px, with 2px trimmed from each side by the gap
+        let grid = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(80.0, 100.0));
+        let cell = |id: &str| {
+            let (_, _, column, row, columns, rows) = NUMPAD_KEYS
+                .iter()
+                .find(|(key, ..)| *key == id)
+                .copied()
+                .unwrap();
+            NumpadGrid::cell_rect(grid, (column, row, columns, rows))
+        };
+
+        assert_eq!(cell("Kp7").min, egui::pos2(2.0, 22.0));
+        assert_eq!(cell("Kp0").width(), 36.0);
+        assert_eq!(cell("KpReturn").height(), 36.0);
+        assert!(NumpadGrid::contains(&KeyId("KpMinus".into())));
+        assert!(!NumpadGrid::contains(&KeyId("Minus".into())));
+    }
+}
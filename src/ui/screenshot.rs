@@ -0,0 +1,101 @@
+//! Saving the overlay as a PNG, e.g. for shortcut cheat-sheets in documentation.
+//!
+//! A screenshot is asked for with `screenshot_hotkey`, the tray menu or `--screenshot`; the
+//! overlay then captures its window and crops the transparent border around the keys.
+
+use crate::core::wake::wake;
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use eframe::egui::ColorImage;
+use image::{ImageResult, RgbaImage};
+
+/// Set when a screenshot is requested from outside the overlay (e.g. the tray).
+static SCREENSHOT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Asks the overlay to save a screenshot on its next frame.
+pub fn request_screenshot() {
+    SCREENSHOT_REQUESTED.store(true, Ordering::SeqCst);
+    wake();
+}
+
+/// Returns `true` (once) if a screenshot was requested since the last call.
+pub fn take_screenshot_request() -> bool {
+    SCREENSHOT_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// Returns where a requested screenshot is saved: a timestamped file next to the config,
+/// e.g. `ferriskeys-1760000000.png`.
+pub fn screenshot_path(config_path: &Path) -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    config_path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(format!("ferriskeys-{secs}.png"))
+}
+
+/// Returns the part of `image` that isn't fully transparent, or `None` if nothing is drawn.
+pub fn crop_to_content(image: &ColorImage) -> Option<ColorImage> {
+    let [width, height] = image.size;
+    let drawn = |x: usize, y: usize| image.pixels[y * width + x].a() > 0;
+
+    let rows: Vec<usize> = (0..height)
+        .filter(|&y| (0..width).any(|x| drawn(x, y)))
+        .collect();
+    let columns: Vec<usize> = (0..width)
+        .filter(|&x| (0..height).any(|y| drawn(x, y)))
+        .collect();
+    let (top, bottom) = (*rows.first()?, *rows.last()?);
+    let (left, right) = (*columns.first()?, *columns.last()?);
+
+    let pixels = (top..=bottom)
+        .flat_map(|y| {
+            image.pixels[y * width + left..=y * width + right]
+                .iter()
+                .copied()
+        })
+        .collect();
+    Some(ColorImage {
+        size: [right - left + 1, bottom - top + 1],
+        pixels,
+    })
+}
+
+/// Writes `image` to `path` as a PNG with straight (unmultiplied) alpha.
+///
+/// # Errors
+/// Returns an error if the file can't be written.
+pub fn save_png(image: &ColorImage, path: &Path) -> ImageResult<()> {
+    let bytes = image
+        .pixels
+        .iter()
+        .flat_map(|pixel| pixel.to_srgba_unmultiplied())
+        .collect();
+    let [width, height] = image.size;
+    RgbaImage::from_raw(width as u32, height as u32, bytes)
+        .expect("pixel count matches the image size")
+        .save_with_format(path, image::ImageFormat::Png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::Color32;
+
+    #[test]
+    fn cropping_keeps_only_drawn_pixels() {
+        let mut image = ColorImage::new([6, 4], Color32::TRANSPARENT);
+        image.pixels[6 + 2] = Color32::RED; // (2, 1)
+        image.pixels[2 * 6 + 4] = Color32::WHITE; // (4, 2)
+
+        let cropped = crop_to_content(&image).unwrap();
+        assert_eq!(cropped.size, [3, 2]);
+        assert_eq!(cropped.pixels[0], Color32::RED);
+        assert_eq!(cropped.pixels[5], Color32::WHITE);
+        assert!(crop_to_content(&ColorImage::new([3, 3], Color32::TRANSPARENT)).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::config::config::{category_name, Config};
+use crate::core::wake::wake;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use eframe::egui::{self, Color32, Context, DragValue, ViewportBuilder, ViewportId};
+
+/// Set when the settings window should be opened (from the tray or `settings_hotkey`).
+static SETTINGS_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Asks the overlay to open the settings window on its next frame.
+pub fn request_settings() {
+    SETTINGS_REQUESTED.store(true, Ordering::SeqCst);
+    wake();
+}
+
+/// Returns whether the settings window was requested since the last call.
+pub fn take_settings_request() -> bool {
+    SETTINGS_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// A small settings window for the options most people want to tweak without editing TOML.
+///
+/// Edits are applied to the running overlay's `Config` as they are made (live preview);
+/// **Save** writes them to the config file and **Revert** reloads the file.
+#[derive(Default)]
+pub struct SettingsWindow {
+    /// Whether the window is currently shown.
+    pub open: bool,
+    /// Result of the last save, shown at the bottom of the window.
+    status: Option<String>,
+}
+
+impl SettingsWindow {
+    /// Shows the settings window in its own viewport while it is open.
+    ///
+    /// # Returns
+    /// `true` if `config` was changed this frame.
+    pub fn show(&mut self, ctx: &Context, config: &mut Config) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        let builder = ViewportBuilder::default()
+            .with_title("FerrisKeys Settings")
+            .with_app_id(format!("{}-settings", config.app_id))
+            .with_inner_size([380.0, 520.0]);
+
+        ctx.show_viewport_immediate(
+            ViewportId::from_hash_of("settings"),
+            builder,
+            |ctx, _class| {
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    self.open = false;
+                }
+
+                egui::CentralPanel::default()
+                    .show(ctx, |ui| {
+                        egui::ScrollArea::vertical()
+                            .show(ui, |ui| self.contents(ui, config))
+                            .inner
+                    })
+                    .inner
+            },
+        )
+    }
+
+    /// Draws the editable fields and the Save/Revert buttons.
+    fn contents(&mut self, ui: &mut egui::Ui, config: &mut Config) -> bool {
+        let mut changed = false;
+
+        ui.heading("General");
+        ui.horizontal(|ui| {
+            ui.label("Timeout (ms)");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut config.timeout_ms)
+                        .range(0..=60_000)
+                        .speed(10),
+                )
+                .changed();
+        });
+
+        ui.separator();
+        ui.heading("Window");
+        ui.horizontal(|ui| {
+            ui.label("Position");
+            changed |= ui.add(DragValue::new(&mut config.position[0])).changed();
+            changed |= ui.add(DragValue::new(&mut config.position[1])).changed();
+        });
+        ui.horizontal(|ui| {
+            ui.label("Size");
+            changed |= ui
+                .add(DragValue::new(&mut config.size[0]).range(1.0..=f32::MAX))
+                .changed();
+            changed |= ui
+                .add(DragValue::new(&mut config.size[1]).range(1.0..=f32::MAX))
+                .changed();
+        });
+        if config.anchor.is_some() {
+            ui.weak("An anchor is set, so the position is ignored.");
+        }
+
+        ui.separator();
+        ui.heading("Styles");
+        let mut categories: Vec<_> = config.styles.keys().cloned().collect();
+        categories.sort_by_key(category_name);
+        for cat in categories {
+            let Some(style) = config.styles.get_mut(&cat) else {
+                continue;
+            };
+            egui::CollapsingHeader::new(category_name(&cat)).show(ui, |ui| {
+                egui::Grid::new(("style", category_name(&cat)))
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (label, value) in [
+                            ("Width", &mut style.width),
+                            ("Height", &mut style.height),
+                            ("Icon size", &mut style.icon_size),
+                            ("Text size", &mut style.text_size),
+                        ] {
+                            ui.label(label);
+                            changed |= ui.add(DragValue::new(value).range(0.0..=1000.0)).changed();
+                            ui.end_row();
+                        }
+                        for (label, color) in [
+                            ("Background", &mut style.bg_color),
+                            ("Foreground", &mut style.fg_color),
+                        ] {
+                            ui.label(label);
+                            changed |= color_edit(ui, color);
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                self.status = Some(match config.save() {
+                    Ok(()) => format!("Saved to {}", config.path.display()),
+                    Err(e) => format!("Failed to save: {e}"),
+                });
+            }
+            if ui.button("Revert").clicked() {
+                match Config::load(&config.path) {
+                    Ok(loaded) => {
+                        *config = loaded;
+                        self.status = None;
+                        changed = true;
+                    }
+                    Err(e) => self.status = Some(format!("Failed to revert: {e}")),
+                }
+            }
+        });
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+
+        changed
+    }
+}
+
+/// Edits a color, including its transparency, in place, returning whether it changed.
+fn color_edit(ui: &mut egui::Ui, color: &mut Color32) -> bool {
+    ui.color_edit_button_srgba(color).changed()
+}
@@ -0,0 +1,70 @@
+use crate::config::config::SoundConfig;
+use crate::input::input::{InputEvent, KeyId};
+use rodio::{Decoder, OutputStream, Source};
+use std::{
+    fs,
+    io::Cursor,
+    sync::mpsc::Receiver,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Minimum gap between two sounds for the same key, so auto-repeat doesn't machine-gun.
+const REPEAT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Spawns a background thread that plays the configured sample on every key press.
+///
+/// The sample is read into memory once; each press decodes a fresh copy so sounds can
+/// overlap. Repeated presses of the same key within `REPEAT_DEBOUNCE` are ignored, which
+/// covers held keys that auto-repeat. Mouse clicks are silent.
+///
+/// # Arguments
+/// * `config` - The `[sound]` settings (sample file path).
+/// * `rx` - A subscription to the input event stream.
+pub fn spawn_sound_player(config: &SoundConfig, rx: Receiver<InputEvent>) {
+    let bytes = match fs::read(&config.file) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to read sound file '{}': {e}", config.file);
+            return;
+        }
+    };
+
+    thread::spawn(move || {
+        // The output stream must stay alive for as long as sounds are played
+        let (_stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                warn!("Failed to open audio output: {e}");
+                return;
+            }
+        };
+
+        let mut last: Option<(KeyId, Instant)> = None;
+
+        for event in rx.iter() {
+            let InputEvent::KeyPress(id, _) = event else {
+                continue;
+            };
+
+            let repeated = last
+                .as_ref()
+                .is_some_and(|(prev, at)| *prev == id && at.elapsed() < REPEAT_DEBOUNCE);
+            last = Some((id, Instant::now()));
+
+            if repeated {
+                continue;
+            }
+
+            match Decoder::new(Cursor::new(bytes.clone())) {
+                Ok(source) => {
+                    handle.play_raw(source.convert_samples()).ok();
+                }
+                Err(e) => {
+                    warn!("Failed to decode sound file: {e}");
+                    return;
+                }
+            }
+        }
+    });
+}
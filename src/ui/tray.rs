@@ -1,4 +1,8 @@
-use std::{path::PathBuf, process::Command, thread};
+use crate::config::config::Config;
+use crate::core::buffer::request_clear;
+use crate::ui::screenshot::request_screenshot;
+use crate::ui::settings::request_settings;
+use std::{path::Path, process::Command, thread};
 use tray_icon::{
     menu::{Menu, MenuEvent, MenuItem},
     TrayIcon, TrayIconBuilder,
@@ -14,33 +18,13 @@ fn load_embedded_icon() -> tray_icon::Icon {
     tray_icon::Icon::from_rgba(vec![0; 4], 1, 1).unwrap()
 }
 
-/// Determines the configuration directory path for the current platform.
-///
-/// - On Windows: uses `C:\Users\<User>\AppData\Roaming\ferriskeys`
-/// - On Unix-like systems: uses `$XDG_CONFIG_HOME/ferriskeys`
-///
-/// # Returns
-/// An `Option<PathBuf>` pointing to the `ferriskeys` configuration directory.
-fn get_config_path() -> Option<PathBuf> {
-    if cfg!(target_os = "windows") {
-        dirs::data_dir().map(|d| d.join("ferriskeys"))
-    } else {
-        dirs::config_dir().map(|d| d.join("ferriskeys"))
-    }
-}
-
 /// Opens the user's configuration folder in the default file explorer.
 ///
 /// Attempts to use platform-specific commands:
 /// - Windows: `explorer`
 /// - macOS: `open`
 /// - Linux/others: `xdg-open`
-fn open_config_folder() {
-    let Some(config_dir) = get_config_path() else {
-        eprintln!("Could not determine config path");
-        return;
-    };
-
+fn open_config_folder(config_dir: &Path) {
     let _ = if cfg!(target_os = "windows") {
         Command::new("explorer").arg(config_dir).spawn()
     } else if cfg!(target_os = "macos") {
@@ -55,27 +39,45 @@ fn open_config_folder() {
 ///
 /// The tray menu includes:
 /// - **"Open Config"**: Opens the configuration directory in a file explorer.
+/// - **"Settings"**: Opens the settings window.
+/// - **"Clear"**: Removes all keys currently shown on the overlay.
+/// - **"Screenshot"**: Saves the overlay as a PNG next to the config file.
 /// - **"Quit"**: Terminates the application.
 ///
 /// The function launches a background thread to listen for menu item events.
 ///
+/// # Arguments
+/// * `explicit_config` - The `--config` path, if given, so "Open Config" opens its folder.
+///
 /// # Returns
 /// `Some(TrayIcon)` if the tray icon was successfully created; `None` otherwise.
-pub fn spawn_tray() -> Option<TrayIcon> {
-    if get_config_path().is_none() {
-        eprintln!("Could not determine config path");
+pub fn spawn_tray(explicit_config: Option<&Path>) -> Option<TrayIcon> {
+    // The folder of the config actually loaded, as `Config::load_auto` picks it
+    let Some(config_dir) =
+        Config::active_path(explicit_config).and_then(|path| path.parent().map(Path::to_path_buf))
+    else {
+        warn!("Could not determine config path");
         return None;
-    }
+    };
 
     // Create tray menu items
     let open_item = MenuItem::new("Open Config", true, None);
+    let settings_item = MenuItem::new("Settings", true, None);
+    let clear_item = MenuItem::new("Clear", true, None);
+    let screenshot_item = MenuItem::new("Screenshot", true, None);
     let quit_item = MenuItem::new("Quit", true, None);
     let open_id = open_item.id().clone();
+    let settings_id = settings_item.id().clone();
+    let clear_id = clear_item.id().clone();
+    let screenshot_id = screenshot_item.id().clone();
     let quit_id = quit_item.id().clone();
 
     // Build the menu and append items
     let menu = Menu::new();
     menu.append(&open_item).unwrap();
+    menu.append(&settings_item).unwrap();
+    menu.append(&clear_item).unwrap();
+    menu.append(&screenshot_item).unwrap();
     menu.append(&quit_item).unwrap();
 
     // Build the tray icon with the specified menu and tooltip
@@ -91,7 +93,13 @@ pub fn spawn_tray() -> Option<TrayIcon> {
     thread::spawn(move || {
         for event in rx.iter() {
             if event.id == open_id {
-                open_config_folder();
+                open_config_folder(&config_dir);
+            } else if event.id == settings_id {
+                request_settings();
+            } else if event.id == clear_id {
+                request_clear();
+            } else if event.id == screenshot_id {
+                request_screenshot();
             } else if event.id == quit_id {
                 std::process::exit(0);
             }
@@ -1,240 +1,611 @@
-use crate::config::config::Config;
-use crate::input::keymap::{
-    category_for_key, normalize_key_label, normalize_mouse_label, KeyCategory::*,
+use crate::config::config::{
+    Animation, Config, GradientDirection, IconPosition, Overflow, Shadow, Style,
 };
+use crate::core::buffer::{entry_style, style_for, KeyBuffer, KeyEntry, SCROLLBACK_LIMIT};
+use crate::input::keymap::{category_for_key, glyph_word, KeyCategory::*};
+use crate::ui::layout::{content_rect, row_top};
 
-use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
-use eframe::egui::{self, FontId, Pos2};
+use eframe::egui::{self, emath::GuiRounding, FontId, Pos2};
 
-/// Represents a single key or mouse input event for visualization.
-#[derive(Clone)]
-pub struct KeyEntry {
-    pub icon: String,  // Optional icon string (e.g., modifier or mouse icon)
-    pub label: String, // Main label text (e.g., "Ctrl", "A", "F5")
-    pub anim: f32,     // Animation progress (0.0 to 1.0)
-    pub time: Instant, // Time of last event (for fading/removal)
-}
+/// Rendering for the key buffer. State changes (pushing, expiry) live in `core::buffer` and
+/// placement in `ui::layout`; this layer only turns them into egui paint calls.
+impl KeyBuffer {
+    /// Renders the current key buffer onto the provided `egui` UI panel.
+    ///
+    /// - Applies per-key styles and the configured entry/exit animations.
+    /// - Clips the display based on available width (and the `max_content_*` caps),
+    ///   trimming or keeping keys per `overflow`.
+    /// - Automatically expires keys older than 1 second, unless `sticky` is set.
+    /// - With `fixed_slots`, draws each key in its own slot instead of packing them.
+    ///
+    /// `elapsed` is the time since the previous frame, which animations advance by.
+    pub fn render(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &Config,
+        max_width: f32,
+        elapsed: Duration,
+    ) {
+        // Keep the row within `max_content_width`/`max_content_height`, clipping anything taller
+        // With a `panel`, keys are inset by its `content_padding`, but the panel itself may
+        // use the full content area
+        let content = content_rect(config, ui.max_rect(), max_width);
+        let keys = match config.panel {
+            Some(panel) => content.shrink2(egui::vec2(panel.content_padding, 0.0)),
+            None => content,
+        };
+        let max_width = keys.width();
+        let mut child = ui.new_child(egui::UiBuilder::new().max_rect(keys));
+        child.set_clip_rect(content.intersect(ui.clip_rect()));
+        let ui = &mut child;
 
-/// A ring buffer of currently visible keys to render onscreen.
-///
-/// Used to store and display a limited set of recent inputs.
-pub struct KeyBuffer {
-    pub keys: VecDeque<KeyEntry>,
-}
+        // Pick up slot count changes (including hot-reloads)
+        self.set_slot_count(config.fixed_slots);
 
-impl KeyBuffer {
-    /// Creates a new, empty key buffer.
-    pub fn new() -> Self {
-        Self {
-            keys: VecDeque::new(),
+        // Measure labels so `auto_width`/`min_width`/`max_width` styles can size boxes to them
+        self.measure(ui, config);
+        if !self.slots.is_empty() {
+            self.render_slots(ui, config, max_width, elapsed);
+            return;
+        }
+
+        // Remove expired keys; sticky keys stay until the next press
+        if !config.sticky {
+            let timeout = Duration::from_millis(config.timeout_ms);
+            self.expire(Instant::now(), timeout, config.exit_animation);
+        }
+
+        // Advance entry animations by the time since the last frame (keys that don't fit are
+        // trimmed below anyway)
+        let max_step = Duration::from_millis(config.max_animation_step_ms);
+        let frames = self.animation_frames(elapsed, max_step);
+        self.advance_animations(frames);
+
+        // Compute which keys fit and where, then render each key visual
+        let draw_list = self.layout(config, ui.max_rect(), max_width);
+        paint_panel(ui, config, draw_list.iter().map(|(_, rect)| *rect));
+        for (i, (key, rect)) in draw_list.iter().enumerate() {
+            let style = entry_style(config, key);
+            let newest = i + 1 == draw_list.len();
+            paint_key(
+                ui,
+                key,
+                &style,
+                *rect,
+                config,
+                newest && config.highlight_latest,
+            );
+        }
+
+        // Trim excess keys from buffer that didn't fit onscreen, or keep them to scroll back
+        match config.overflow {
+            Overflow::Trim => self.trim_to(draw_list.len()),
+            Overflow::Scroll => self.trim_to(SCROLLBACK_LIMIT),
+        }
+    }
+
+    /// Updates each key's measured `text_width` for its current style and font.
+    fn measure(&mut self, ui: &egui::Ui, config: &Config) {
+        for key in self.keys.iter_mut().chain(self.slots.iter_mut().flatten()) {
+            let style = style_for(config, &key.label);
+            if style.sized_to_label() {
+                key.text_width = content_width(ui, key, &style, config);
+            }
         }
     }
 
-    /// Adds a new key or mouse input to the buffer.
+    /// Renders keys into fixed positional slots spread evenly across `max_width`.
     ///
-    /// - Prevents duplicate key labels by refreshing existing ones.
-    /// - Normalizes labels using platform-specific logic.
-    /// - Parses label into icon + text if applicable (e.g., `"⇧ Shift"`).
-    pub fn push_key(&mut self, _unused_icon: &str, label: &str, mouse: bool) {
-        // Check if label already exists and refresh its time/animation if found
-        if let Some(existing) = self.keys.iter_mut().find(|k| k.label == label) {
-            existing.time = Instant::now();
-            existing.anim = 0.8;
-            return;
+    /// Expired keys are blanked in place (unless `sticky` is set), so the remaining
+    /// keys keep their position instead of collapsing to the right.
+    fn render_slots(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &Config,
+        max_width: f32,
+        elapsed: Duration,
+    ) {
+        if !config.sticky {
+            let timeout = Duration::from_millis(config.timeout_ms);
+            self.expire(Instant::now(), timeout, config.exit_animation);
         }
+        let max_step = Duration::from_millis(config.max_animation_step_ms);
+        let frames = self.animation_frames(elapsed, max_step);
 
-        // Normalize the input label for consistency
-        let raw = if !mouse {
-            normalize_key_label(label).to_string()
-        } else {
-            normalize_mouse_label(label).to_string()
-        };
+        let slot_width = max_width / self.slots.len() as f32;
+        let left = ui.max_rect().right() - max_width;
+        let row_height = self
+            .slots
+            .iter()
+            .flatten()
+            .map(|key| entry_style(config, key).height)
+            .fold(0.0, f32::max);
+        let newest = self.slots.iter().flatten().map(|key| key.time).max();
 
-        // Strip known key prefixes for better UI clarity
-        let label = match &raw {
-            l if l.starts_with("Key") => &l[3..],
-            l if l.starts_with("Num")
-                && l.len() == 4
-                && l[3..].chars().all(|c| c.is_ascii_digit()) =>
-            {
-                &l[3..]
-            }
-            _ => &raw,
-        };
+        // The panel spans the occupied slots, which are only known once placed below
+        let panel = ui.painter().add(egui::Shape::Noop);
+        let mut placed = Vec::new();
 
-        // Attempt to split icon and label by the first space
-        let (icon, label_text) = if let Some(space_idx) = label.find(' ') {
-            label.split_at(space_idx)
-        } else {
-            ("", label)
-        };
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            let Some(key) = slot else {
+                continue;
+            };
 
-        let icon = icon.trim();
-        let label_clean = label_text.trim();
+            key.advance_animation(frames);
 
-        // Format label text: e.g., F1, F12 stay uppercase, others retain formatting
-        let formatted_label = if label_clean.to_lowercase().starts_with("f") {
-            label_clean.to_uppercase()
-        } else {
-            label_clean.to_string()
-        };
+            // Center the key within its slot, and align it within the row
+            let style = entry_style(config, key);
+            let x = left + i as f32 * slot_width + (slot_width - style.width) / 2.0;
+            let rect = egui::Rect::from_min_size(
+                egui::pos2(
+                    x,
+                    row_top(config.row_align, ui.max_rect(), row_height, style.height),
+                ),
+                egui::vec2(style.width, style.height),
+            );
+            let highlight = config.highlight_latest && Some(key.time) == newest;
+            paint_key(ui, key, &style, rect, config, highlight);
+            placed.push(rect);
+        }
 
-        // Add the newly created entry to the buffer
-        self.keys.push_back(KeyEntry {
-            icon: icon.to_string(),
-            label: formatted_label,
-            anim: 0.8,
-            time: Instant::now(),
-        });
+        if let Some(shape) = panel_shape(config, placed.into_iter()) {
+            ui.painter().set(panel, shape);
+        }
     }
+}
 
-    /// Renders the current key buffer onto the provided `egui` UI panel.
-    ///
-    /// - Applies per-key styles and animation.
-    /// - Clips the display based on available width.
-    /// - Automatically expires keys older than 1 second.
-    pub fn render(&mut self, ui: &mut egui::Ui, config: &Config, max_width: f32) {
-        let padding = 8.0;
-        let mut total_width = 0.0;
-        let mut draw_list = vec![];
-
-        // Remove expired keys (older than 1 second)
-        let now = Instant::now();
-        self.keys
-            .retain(|k| now.duration_since(k.time) < Duration::from_secs(1));
-
-        // Determine which keys can fit on the screen from right to left
-        for key in self.keys.iter_mut().rev() {
-            let category = category_for_key(&key.label);
-            let style = config
-                .styles
-                .get(&category)
-                .cloned()
-                .unwrap_or_else(Config::fallback_style);
-
-            let width = style.width + padding;
-
-            if total_width + width > max_width {
-                break;
+/// Paints the configured backing `panel` around `rects`, beneath anything painted after it.
+fn paint_panel(ui: &egui::Ui, config: &Config, rects: impl Iterator<Item = egui::Rect>) {
+    if let Some(shape) = panel_shape(config, rects) {
+        ui.painter().add(shape);
+    }
+}
+
+/// Returns the backing `panel` enclosing `rects`, if one is configured and there are keys.
+///
+/// Only the panel's own color (and opacity) is used, so it can be translucent under opaque
+/// keys or the other way round.
+fn panel_shape(config: &Config, rects: impl Iterator<Item = egui::Rect>) -> Option<egui::Shape> {
+    let panel = config.panel?;
+    let bounds = rects.reduce(|a, b| a.union(b))?;
+    Some(egui::Shape::rect_filled(
+        bounds.expand2(egui::vec2(panel.content_padding, panel.padding)),
+        egui::CornerRadius::same(panel.rounding.min(255.0) as u8),
+        panel.color,
+    ))
+}
+
+/// Paints a single key box, with its icon and label, inside `slot`.
+///
+/// With `compact`, icon and label share one centered line regardless of category.
+/// With `highlight`, the box is tinted and outlined in the `highlight_color`.
+fn paint_key(
+    ui: &egui::Ui,
+    key: &KeyEntry,
+    style: &Style,
+    slot: egui::Rect,
+    config: &Config,
+    highlight: bool,
+) {
+    let category = category_for_key(&key.label);
+    let compact = config.compact;
+
+    // Apply the entry/exit animations: scale and slide the box, and fade its colors
+    let (rect, alpha) = animate(key, slot, config);
+    let rect = if config.pixel_snap {
+        rect.round_to_pixels(ui.pixels_per_point())
+    } else {
+        rect
+    };
+    let mut style = faded(style, alpha);
+    let accent = config.highlight_color.gamma_multiply(alpha);
+    if highlight {
+        style.bg_color = style.bg_color.lerp_to_gamma(accent, 0.25);
+        style.bg_color2 = style.bg_color2.map(|c| c.lerp_to_gamma(accent, 0.25));
+    }
+    if config.velocity_visualization && key.velocity > 0.0 {
+        let glow = egui::Color32::WHITE.gamma_multiply(alpha);
+        let t = VELOCITY_GLOW * key.velocity;
+        style.bg_color = style.bg_color.lerp_to_gamma(glow, t);
+        style.bg_color2 = style.bg_color2.map(|c| c.lerp_to_gamma(glow, t));
+    }
+    let style = &style;
+    let painter = ui.painter_at(rect);
+
+    // Drop shadow, then background
+    if let Some(shadow) = &style.shadow {
+        paint_shadow(ui, rect, shadow);
+    }
+    match style.bg_color2 {
+        Some(bg_color2) => paint_gradient(ui, rect, style, bg_color2),
+        None => {
+            painter.rect_filled(rect, egui::CornerRadius::same(8), style.bg_color);
+        }
+    }
+    if highlight && config.highlight_border > 0.0 {
+        painter.rect_stroke(
+            rect,
+            egui::CornerRadius::same(8),
+            egui::Stroke::new(config.highlight_border, accent),
+            egui::StrokeKind::Inside,
+        );
+    }
+
+    let icon_font = FontId::proportional(style.icon_size);
+    let text_font = FontId::proportional(style.text_size);
+    let (icon_text, main_text) = display_texts(ui, key, style, config);
+
+    // Render logic by category
+    match category {
+        _ if compact => {
+            let line = if icon_text.is_empty() {
+                main_text.to_string()
+            } else {
+                format!("{} {}", icon_text, main_text)
+            };
+            paint_text(
+                &painter,
+                style,
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                &line,
+                text_font.clone(),
+                config.pixel_snap,
+            );
+        }
+        Normal | Numeric | Symbol | Navigation | Function => {
+            paint_text(
+                &painter,
+                style,
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                main_text,
+                text_font.clone(),
+                config.pixel_snap,
+            );
+        }
+        _ if !icon_text.is_empty() && style.icon_position != IconPosition::Above => {
+            paint_icon_and_label(
+                &painter,
+                style,
+                rect,
+                (icon_text, icon_font.clone()),
+                (main_text, text_font.clone()),
+                config.pixel_snap,
+            );
+        }
+        Modifier => {
+            if !icon_text.is_empty() {
+                paint_text(
+                    &painter,
+                    style,
+                    Pos2::new(rect.right() - 10.0, rect.top() + 10.0),
+                    egui::Align2::RIGHT_TOP,
+                    icon_text,
+                    icon_font.clone(),
+                    config.pixel_snap,
+                );
             }
 
-            if key.anim < 1.0 {
-                key.anim += 0.1;
+            paint_text(
+                &painter,
+                style,
+                Pos2::new(rect.right() - 10.0, rect.bottom() - 10.0),
+                egui::Align2::RIGHT_BOTTOM,
+                main_text,
+                text_font.clone(),
+                config.pixel_snap,
+            );
+        }
+        Scrollable | Editor | Escape | AltFunction | Mouse => {
+            if !icon_text.is_empty() {
+                paint_text(
+                    &painter,
+                    style,
+                    Pos2::new(rect.right() - 47.5, rect.top() + 20.0),
+                    egui::Align2::CENTER_CENTER,
+                    icon_text,
+                    icon_font.clone(),
+                    config.pixel_snap,
+                );
             }
 
-            total_width += width;
-            draw_list.push(key.clone());
-        }
-
-        // Draw from left to right (restore original order)
-        draw_list.reverse();
-        let mut x = ui.max_rect().right() - total_width;
-
-        // Render each key visual
-        for key in &draw_list {
-            let category = category_for_key(&key.label);
-            let style = config
-                .styles
-                .get(&category)
-                .cloned()
-                .unwrap_or_else(Config::fallback_style);
-
-            // Apply animation scaling
-            let scale = key.anim.min(1.0);
-            let size = egui::vec2(style.width * scale, style.height * scale);
-            let top_left = egui::pos2(
-                x + (style.width - size.x) / 2.0,
-                (style.height - size.y) / 2.0,
+            paint_text(
+                &painter,
+                style,
+                Pos2::new(rect.right() - 45.0, rect.bottom() - 20.0),
+                egui::Align2::CENTER_CENTER,
+                main_text,
+                text_font.clone(),
+                config.pixel_snap,
             );
-            let rect = egui::Rect::from_min_size(top_left, size);
-            let painter = ui.painter_at(rect);
+        }
+        _ => {
+            if !icon_text.is_empty() {
+                paint_text(
+                    &painter,
+                    style,
+                    Pos2::new(rect.center().x, rect.top() + 18.0),
+                    egui::Align2::CENTER_CENTER,
+                    icon_text,
+                    icon_font.clone(),
+                    config.pixel_snap,
+                );
+            }
 
-            // Background
-            painter.rect_filled(rect, egui::CornerRadius::same(8), style.bg_color);
+            paint_text(
+                &painter,
+                style,
+                Pos2::new(rect.center().x, rect.bottom() - 26.0),
+                egui::Align2::CENTER_CENTER,
+                main_text,
+                text_font.clone(),
+                config.pixel_snap,
+            );
+        }
+    }
+}
 
-            let icon_text = &key.icon;
-            let main_text = &key.label;
-
-            // Render logic by category
-            match category {
-                Normal | Numeric | Symbol | Navigation | Function => {
-                    painter.text(
-                        rect.center(),
-                        egui::Align2::CENTER_CENTER,
-                        main_text,
-                        FontId::proportional(style.text_size),
-                        style.fg_color,
-                    );
-                }
-                Modifier => {
-                    if !icon_text.is_empty() {
-                        painter.text(
-                            Pos2::new(rect.right() - 10.0, rect.top() + 10.0),
-                            egui::Align2::RIGHT_TOP,
-                            icon_text,
-                            FontId::proportional(style.icon_size),
-                            style.fg_color,
-                        );
-                    }
-
-                    painter.text(
-                        Pos2::new(rect.right() - 10.0, rect.bottom() - 10.0),
-                        egui::Align2::RIGHT_BOTTOM,
-                        main_text,
-                        FontId::proportional(style.text_size),
-                        style.fg_color,
-                    );
-                }
-                Scrollable | Editor | Escape | AltFunction | Mouse => {
-                    if !icon_text.is_empty() {
-                        painter.text(
-                            Pos2::new(rect.right() - 47.5, rect.top() + 20.0),
-                            egui::Align2::CENTER_CENTER,
-                            icon_text,
-                            FontId::proportional(style.icon_size),
-                            style.fg_color,
-                        );
-                    }
-
-                    painter.text(
-                        Pos2::new(rect.right() - 45.0, rect.bottom() - 20.0),
-                        egui::Align2::CENTER_CENTER,
-                        main_text,
-                        FontId::proportional(style.text_size),
-                        style.fg_color,
-                    );
-                }
-                _ => {
-                    if !icon_text.is_empty() {
-                        painter.text(
-                            Pos2::new(rect.center().x, rect.top() + 18.0),
-                            egui::Align2::CENTER_CENTER,
-                            icon_text,
-                            FontId::proportional(style.icon_size),
-                            style.fg_color,
-                        );
-                    }
-
-                    painter.text(
-                        Pos2::new(rect.center().x, rect.bottom() - 26.0),
-                        egui::Align2::CENTER_CENTER,
-                        main_text,
-                        FontId::proportional(style.text_size),
-                        style.fg_color,
-                    );
-                }
-            }
+/// Returns the icon and label text drawn for `key`, falling back to words for glyphs the
+/// loaded fonts can't draw (and no icon), and translating words with `[locale]`.
+///
+/// Translation happens only here, so categories (and styles) still follow the English name.
+fn display_texts<'a>(
+    ui: &egui::Ui,
+    key: &'a KeyEntry,
+    style: &Style,
+    config: &'a Config,
+) -> (&'a str, &'a str) {
+    let icon_font = FontId::proportional(style.icon_size);
+    let text_font = FontId::proportional(style.text_size);
+    let icon_text = if ui.fonts(|f| f.has_glyphs(&icon_font, &key.icon)) {
+        key.icon.as_str()
+    } else {
+        ""
+    };
+    let main_text = if ui.fonts(|f| f.has_glyphs(&text_font, &key.label)) {
+        key.label.as_str()
+    } else {
+        glyph_word(&key.label).unwrap_or(&key.label)
+    };
+    let main_text = config
+        .locale
+        .get(main_text)
+        .map_or(main_text, String::as_str);
+    (icon_text, main_text)
+}
+
+/// Measures how wide `key`'s icon and label are as `paint_key` lays them out: side by side
+/// when compact or with a left/right `icon_position`, otherwise stacked.
+fn content_width(ui: &egui::Ui, key: &KeyEntry, style: &Style, config: &Config) -> f32 {
+    let (icon, label) = display_texts(ui, key, style, config);
+    let width = |text: &str, size: f32| {
+        if text.is_empty() {
+            return 0.0;
+        }
+        let font = FontId::proportional(size);
+        ui.fonts(|f| {
+            f.layout_no_wrap(text.to_string(), font, style.fg_color)
+                .size()
+                .x
+        })
+    };
+
+    // Only some categories draw their icon outside compact mode
+    let draws_icon = config.compact
+        || !matches!(
+            category_for_key(&key.label),
+            Normal | Numeric | Symbol | Navigation | Function
+        );
+    let label_width = width(label, style.text_size);
+    let icon_width = if draws_icon {
+        width(icon, style.icon_size)
+    } else {
+        0.0
+    };
+
+    let side_by_side = config.compact
+        || matches!(
+            style.icon_position,
+            IconPosition::Left | IconPosition::Right
+        );
+    if side_by_side && icon_width > 0.0 {
+        icon_width + ICON_GAP + label_width
+    } else {
+        icon_width.max(label_width)
+    }
+}
+
+/// Space between an icon and its label when drawn side by side, in pixels.
+const ICON_GAP: f32 = 6.0;
+
+/// Paints an icon and label as one group centered in `rect`, the icon placed on the side
+/// of the label given by `style.icon_position`.
+fn paint_icon_and_label(
+    painter: &egui::Painter,
+    style: &Style,
+    rect: egui::Rect,
+    (icon, icon_font): (&str, FontId),
+    (label, text_font): (&str, FontId),
+    snap: bool,
+) {
+    let size = |text: &str, font: &FontId| {
+        painter
+            .layout_no_wrap(text.to_string(), font.clone(), style.fg_color)
+            .size()
+    };
+    let icon_size = size(icon, &icon_font);
+    let label_size = size(label, &text_font);
+
+    // Each part's center is shifted from the box center by half of the other part plus the gap
+    let center = rect.center();
+    let down = |h: f32| egui::vec2(0.0, (h + ICON_GAP) / 2.0);
+    let right = |w: f32| egui::vec2((w + ICON_GAP) / 2.0, 0.0);
+    let (icon_pos, label_pos) = match style.icon_position {
+        IconPosition::Above => (center - down(label_size.y), center + down(icon_size.y)),
+        IconPosition::Below => (center + down(label_size.y), center - down(icon_size.y)),
+        IconPosition::Left => (center - right(label_size.x), center + right(icon_size.x)),
+        IconPosition::Right => (center + right(label_size.x), center - right(icon_size.x)),
+    };
+
+    let align = egui::Align2::CENTER_CENTER;
+    paint_text(painter, style, icon_pos, align, icon, icon_font, snap);
+    paint_text(painter, style, label_pos, align, label, text_font, snap);
+}
+
+/// Seconds for one dim-and-back cycle of the `pulse_held` effect.
+const PULSE_PERIOD: f32 = 0.8;
+
+/// Extra size of a full-velocity key with `velocity_visualization`, as a fraction.
+const VELOCITY_SCALE: f32 = 0.15;
+
+/// How far a full-velocity key's background is brightened towards white.
+const VELOCITY_GLOW: f32 = 0.35;
 
-            // Advance drawing position for next key
-            x += style.width + padding;
+/// Returns the box to draw `key` in and its opacity, given its entry and exit progress.
+fn animate(key: &KeyEntry, slot: egui::Rect, config: &Config) -> (egui::Rect, f32) {
+    let (mut scale, mut alpha, mut dy) = (1.0, 1.0, 0.0);
+
+    // `shown` runs from 0.0 (hidden) to 1.0 (fully shown)
+    let mut apply = |animation: Animation, shown: f32| match animation {
+        Animation::None => {}
+        Animation::Scale => scale *= shown,
+        Animation::Fade => alpha *= shown,
+        Animation::Slide => {
+            dy += (1.0 - shown) * slot.height() / 2.0;
+            alpha *= shown;
         }
+        Animation::Pop => scale *= shown + 0.5 * (std::f32::consts::PI * shown).sin(),
+    };
+    apply(config.entry_animation, key.anim.min(1.0));
+    if let Some(exit) = key.exit {
+        apply(config.exit_animation, 1.0 - exit);
+    }
+
+    // `pulse_held` dims held keys and back in a loop, easing to steady once released
+    if config.pulse_held && key.pulse > 0.0 {
+        let phase = key.time.elapsed().as_secs_f32() / PULSE_PERIOD * std::f32::consts::TAU;
+        alpha *= 1.0 - 0.5 * key.pulse * (0.5 - 0.5 * phase.cos());
+    }
+
+    // `velocity_visualization` grows quickly pressed keys, up to `VELOCITY_SCALE` larger
+    if config.velocity_visualization {
+        scale *= 1.0 + VELOCITY_SCALE * key.velocity;
+    }
+
+    let rect =
+        egui::Rect::from_center_size(slot.center() + egui::vec2(0.0, dy), slot.size() * scale);
+    (rect, alpha.clamp(0.0, 1.0))
+}
+
+/// Returns a copy of `style` with every color faded to `alpha` opacity.
+fn faded(style: &Style, alpha: f32) -> Style {
+    let mut style = style.clone();
+    style.bg_color = style.bg_color.gamma_multiply(alpha);
+    style.fg_color = style.fg_color.gamma_multiply(alpha);
+    style.bg_color2 = style.bg_color2.map(|c| c.gamma_multiply(alpha));
+    if let Some(shadow) = &mut style.shadow {
+        shadow.color = shadow.color.gamma_multiply(alpha);
+    }
+    if let Some(outline) = &mut style.text_outline {
+        outline.color = outline.color.gamma_multiply(alpha);
+    }
+    style
+}
+
+/// Paints `text` in the style's foreground color, first drawing it offset all around
+/// in the `text_outline` color (when set) so it stays readable on any background.
+///
+/// With `snap`, the text is moved so its top-left corner sits on a whole pixel.
+pub fn paint_text(
+    painter: &egui::Painter,
+    style: &Style,
+    pos: Pos2,
+    align: egui::Align2,
+    text: &str,
+    font: FontId,
+    snap: bool,
+) {
+    let (pos, align) = if snap {
+        let size = painter
+            .layout_no_wrap(text.to_string(), font.clone(), style.fg_color)
+            .size();
+        let min = align.anchor_size(pos, size).min;
+        (
+            min.round_to_pixels(painter.pixels_per_point()),
+            egui::Align2::LEFT_TOP,
+        )
+    } else {
+        (pos, align)
+    };
 
-        // Trim excess keys from buffer that didn't fit onscreen
-        let allowed_count = draw_list.len();
-        while self.keys.len() > allowed_count {
-            self.keys.pop_front();
+    if let Some(outline) = &style.text_outline {
+        const DIRECTIONS: [(f32, f32); 8] = [
+            (-1.0, -1.0),
+            (0.0, -1.0),
+            (1.0, -1.0),
+            (-1.0, 0.0),
+            (1.0, 0.0),
+            (-1.0, 1.0),
+            (0.0, 1.0),
+            (1.0, 1.0),
+        ];
+        for (dx, dy) in DIRECTIONS {
+            let offset = egui::vec2(dx, dy) * outline.width;
+            painter.text(pos + offset, align, text, font.clone(), outline.color);
         }
     }
+
+    painter.text(pos, align, text, font, style.fg_color);
+}
+
+/// Fakes a blurred drop shadow by stacking progressively larger, fainter rounded rects.
+fn paint_shadow(ui: &egui::Ui, rect: egui::Rect, shadow: &Shadow) {
+    const LAYERS: usize = 4;
+
+    let base = rect.translate(egui::vec2(shadow.offset[0], shadow.offset[1]));
+    let painter = ui.painter_at(base.expand(shadow.blur));
+    let layer_color = shadow.color.gamma_multiply(1.0 / LAYERS as f32);
+
+    for i in 0..LAYERS {
+        let spread = shadow.blur * (i + 1) as f32 / LAYERS as f32;
+        painter.rect_filled(
+            base.expand(spread),
+            egui::CornerRadius::same((8.0 + spread).min(255.0) as u8),
+            layer_color,
+        );
+    }
+}
+
+/// Fills the rounded key box with a two-color gradient.
+///
+/// The rounded rect is tessellated into a mesh and each vertex is recolored by its
+/// position along the gradient, keeping the anti-aliased edge alpha intact.
+fn paint_gradient(ui: &egui::Ui, rect: egui::Rect, style: &Style, bg_color2: egui::Color32) {
+    let ctx = ui.ctx();
+    let mut tessellator = egui::epaint::Tessellator::new(
+        ctx.pixels_per_point(),
+        ctx.tessellation_options(|o| *o),
+        ctx.fonts(|f| f.font_image_size()),
+        Vec::new(),
+    );
+
+    let mut mesh = egui::Mesh::default();
+    let shape =
+        egui::epaint::RectShape::filled(rect, egui::CornerRadius::same(8), egui::Color32::WHITE);
+    tessellator.tessellate_rect(&shape, &mut mesh);
+
+    for vertex in mesh.vertices.iter_mut() {
+        let t = match style.gradient_direction {
+            GradientDirection::Vertical => (vertex.pos.y - rect.top()) / rect.height(),
+            GradientDirection::Horizontal => (vertex.pos.x - rect.left()) / rect.width(),
+        };
+        let color = style.bg_color.lerp_to_gamma(bg_color2, t.clamp(0.0, 1.0));
+        // Feathered edge vertices carry their coverage in alpha
+        vertex.color = color.gamma_multiply(vertex.color.a() as f32 / 255.0);
+    }
+
+    ui.painter_at(rect.expand(1.0)).add(mesh);
 }
@@ -1,102 +1,734 @@
-use crate::config::config::Config;
-use crate::input::input::InputEvent;
-use crate::ui::ui::KeyBuffer;
+use crate::config::config::{Config, DisplayMode, RenderMode, SortSimultaneous, Trigger};
+use crate::config::theme::Theme;
+use crate::core::buffer::{clear_requests, format_label, press_velocity, KeyBuffer};
+use crate::core::chord::ChordFilter;
+use crate::core::debounce::Debouncer;
+use crate::core::wake::frame_drawn;
+use crate::input::focus::{last_focused_window, watch_focus};
+use crate::input::input::{
+    cursor_position, listener_failure, set_wake_on_cursor, InputEvent, KeyId, FOLLOW_INTERVAL,
+};
+use crate::input::keymap::{category_for_key, KeyCategory};
+use crate::input::layout::KeyboardLayout;
+use crate::input::unmapped::{log_unmapped, unmapped_log_path};
+use crate::ui::blur::blur_behind;
+use crate::ui::caption::Caption;
+use crate::ui::monitor::{
+    clamp_to_monitor, detect_monitors, resolve_clamped_position, resolve_position, MonitorRect,
+};
+use crate::ui::numpad::NumpadGrid;
+use crate::ui::screenshot::{
+    crop_to_content, request_screenshot, save_png, screenshot_path, take_screenshot_request,
+};
+use crate::ui::settings::{request_settings, take_settings_request, SettingsWindow};
 
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use eframe::{
     egui,
-    egui::{CentralPanel, Color32, Context, Frame, Rgba, ViewportCommand, Visuals},
+    egui::{
+        CentralPanel, Color32, Context, Frame, Rgba, ViewportBuilder, ViewportCommand, ViewportId,
+        Visuals,
+    },
     App,
 };
 
 /// The main egui application struct responsible for rendering and updating
 /// the live input visualisation overlay.
 pub struct VisualiserApp {
-    pub config: Config,           // User configuration (position, size, styles, etc.)
-    pub rx: Receiver<InputEvent>, // Channel receiver for input events (keys, mouse)
-    pub key_buffer: KeyBuffer,    // Circular buffer of visible keys to render
-    pub recently_seen: HashSet<String>, // Used to debounce repeat events within short intervals
-    pub last_clear: Instant,      // Timer for clearing the recently_seen cache
-    pub last_ui_width: f32,       // Tracks the last available UI width (used for layout)
+    config: Config,              // User configuration (position, size, styles, etc.)
+    rx: Receiver<InputEvent>,    // Channel receiver for input events (keys, mouse)
+    key_buffer: KeyBuffer,       // Circular buffer of visible keys to render
+    caption: Caption,            // Typed text shown with `display_mode = "caption"`
+    numpad: NumpadGrid,          // Numpad keys shown in place with `show_numpad_grid`
+    debouncer: Debouncer,        // Filters key repeats and detects double-clicks
+    chords: ChordFilter,         // Tracks held modifiers for chords and the clear hotkey
+    clears_handled: usize,       // Clear requests (e.g. from the tray) already applied
+    last_ui_width: f32,          // Tracks the last available UI width (used for layout)
+    last_frame: Instant,         // When the last frame was drawn (for animations and the FPS cap)
+    last_follow: Instant,        // When the overlay last moved to the cursor (for `follow_cursor`)
+    last_press: Option<Instant>, // When the last key was shown (for `velocity_visualization`)
+    origin: [f32; 2],            // Configured position resolved to absolute desktop pixels
+    monitors: Option<Vec<MonitorRect>>, // Monitors `follow_cursor` keeps the overlay on
+    offset: [f32; 2], // Offset from the configured position (non-zero for mirrored overlays)
+    mirrors: Vec<Arc<Mutex<VisualiserApp>>>, // Overlays mirrored onto other monitors
+    mirror: bool,     // Whether this is a mirror, which takes reloads from the main overlay
+    config_changed: bool, // The main overlay handed this mirror a new config to apply
+    layout: Option<KeyboardLayout>, // Keyboard layout in use, shown with `show_layout`
+    settings: SettingsWindow, // Settings window, opened from the tray
+    preview: bool,    // Keep sample keys of every style on screen (`--preview`)
+    unmapped: HashSet<KeyId>, // Unknown keys already handled by `log_unmapped`
+    theme: Option<usize>, // Index of the `theme_cycle` theme applied, if any
+    grabbing: bool,   // Whether `grab_modifier` is held, making the overlay draggable
+    dragged: bool,    // The overlay was dragged away from its configured position
+    screenshot_to: Option<PathBuf>, // `--screenshot` file, saved once keys are shown
+    screenshot_pending: bool, // A screenshot was asked of the window and not yet received
 }
 
+/// Keys pressed within this long of each other count as simultaneous for `sort_simultaneous`.
+const SIMULTANEOUS_WINDOW: Duration = Duration::from_millis(50);
+
 impl VisualiserApp {
     /// Creates a new instance of the visualiser app with the given config and input receiver.
     pub fn new(config: Config, rx: Receiver<InputEvent>) -> Self {
         Self {
+            origin: resolve_position(&config),
             config,
             rx,
             key_buffer: KeyBuffer::new(),
-            recently_seen: HashSet::new(),
-            last_clear: Instant::now(),
+            caption: Caption::new(),
+            numpad: NumpadGrid::new(),
+            debouncer: Debouncer::new(),
+            chords: ChordFilter::new(),
+            clears_handled: clear_requests(),
             last_ui_width: 0.0,
+            last_frame: Instant::now(),
+            last_follow: Instant::now(),
+            last_press: None,
+            monitors: None,
+            offset: [0.0, 0.0],
+            mirrors: Vec::new(),
+            mirror: false,
+            config_changed: false,
+            layout: None,
+            settings: SettingsWindow::default(),
+            preview: false,
+            unmapped: HashSet::new(),
+            theme: None,
+            grabbing: false,
+            dragged: false,
+            screenshot_to: None,
+            screenshot_pending: false,
         }
     }
-}
 
-impl App for VisualiserApp {
-    /// Called every frame to update the application state and render the UI.
-    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
-        // Reload config if the file has changed on disk
-        if self.config.maybe_reload() {
-            // Reapply size, position, focus, and mouse passthrough
-            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(egui::pos2(
-                self.config.position[0],
-                self.config.position[1],
-            )));
+    /// Creates a mirrored overlay shown at the configured position shifted by `offset`.
+    pub(crate) fn mirrored(config: Config, rx: Receiver<InputEvent>, offset: [f32; 2]) -> Self {
+        Self {
+            offset,
+            mirror: true,
+            ..Self::new(config, rx)
+        }
+    }
+
+    /// Attaches overlays that mirror this one on other monitors.
+    pub(crate) fn with_mirrors(mut self, mirrors: Vec<VisualiserApp>) -> Self {
+        self.mirrors = mirrors
+            .into_iter()
+            .map(|m| Arc::new(Mutex::new(m)))
+            .collect();
+        self
+    }
+
+    /// Records the keyboard layout the input listener uses, for `show_layout`.
+    pub fn with_layout(mut self, layout: KeyboardLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Enables `--preview`, keeping a sample key of every style on screen.
+    pub fn with_preview(mut self, preview: bool) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Enables `--screenshot`, saving the overlay to `path` once keys are shown and exiting.
+    pub(crate) fn with_screenshot(mut self, path: Option<PathBuf>) -> Self {
+        self.screenshot_to = path;
+        self
+    }
+
+    /// Returns the settings in use, including any hot-reloads applied since.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the settings for changing them in place; changes apply from the next event
+    /// or frame. Window settings (position, size, ...) only affect the FerrisKeys overlay.
+    pub fn config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
+    /// Returns the keys currently shown.
+    pub fn key_buffer(&self) -> &KeyBuffer {
+        &self.key_buffer
+    }
+
+    /// Returns the on-screen position of this overlay (resolved position plus offset).
+    fn position(&self) -> egui::Pos2 {
+        egui::pos2(
+            self.origin[0] + self.offset[0],
+            self.origin[1] + self.offset[1],
+        )
+    }
+
+    /// Returns whether clicks should pass through right now: per the window flags, and
+    /// never while `grab_modifier` is held.
+    fn mouse_passthrough(&self) -> bool {
+        self.config.mouse_passthrough() && !self.grabbing
+    }
+
+    /// Returns the viewport id used for the mirrored overlay at `index`.
+    pub(crate) fn mirror_viewport_id(index: usize) -> ViewportId {
+        ViewportId::from_hash_of(("mirror", index))
+    }
+
+    /// Shows each mirrored overlay in its own borderless, click-through viewport.
+    fn show_mirrors(&self, ctx: &Context) {
+        for (i, mirror) in self.mirrors.iter().enumerate() {
+            let builder = {
+                let m = mirror.lock().unwrap();
+                ViewportBuilder::default()
+                    .with_title(&m.config.window_title)
+                    .with_app_id(&m.config.app_id)
+                    .with_decorations(m.config.decorations)
+                    .with_resizable(m.config.resizable)
+                    .with_transparent(true)
+                    .with_window_level(m.config.window_level())
+                    .with_taskbar(!m.config.skip_taskbar)
+                    .with_window_type(m.config.x11_window_type())
+                    .with_mouse_passthrough(m.config.mouse_passthrough())
+                    .with_inner_size(m.config.size)
+                    .with_position(m.position())
+            };
+
+            let mirror = mirror.clone();
+            ctx.show_viewport_deferred(Self::mirror_viewport_id(i), builder, move |ctx, _class| {
+                mirror.lock().unwrap().draw(ctx)
+            });
+        }
+    }
+
+    /// Shows the settings window when open, applying edits to this overlay and its mirrors.
+    fn show_settings(&mut self, ctx: &Context) {
+        if take_settings_request() {
+            self.settings.open = true;
+        }
+        if !self.settings.show(ctx, &mut self.config) {
+            return;
+        }
+
+        self.origin = resolve_clamped_position(&self.config);
+        self.dragged = false;
+        ctx.send_viewport_cmd(ViewportCommand::OuterPosition(self.position()));
+        ctx.send_viewport_cmd(ViewportCommand::InnerSize(egui::vec2(
+            self.config.size[0],
+            self.config.size[1],
+        )));
+
+        self.update_mirrors(ctx);
+        ctx.request_repaint();
+    }
+
+    /// Hands the mirrors this overlay's config, after a reload or settings edit; each
+    /// applies it on its next frame.
+    fn update_mirrors(&self, ctx: &Context) {
+        for (i, mirror) in self.mirrors.iter().enumerate() {
+            let mut mirror = mirror.lock().unwrap();
+            mirror.config = self.config.clone();
+            mirror.config_changed = true;
+            ctx.request_repaint_of(Self::mirror_viewport_id(i));
+        }
+    }
+
+    /// Asks the window for a screenshot when one is requested (or, with `--screenshot`,
+    /// once keys are shown), and saves it as a PNG cropped to the keys when it arrives.
+    fn take_screenshot(&mut self, ctx: &Context) {
+        let ready = self.screenshot_to.is_some() && self.key_buffer.is_fully_shown();
+        if !self.screenshot_pending && (take_screenshot_request() || ready) {
+            self.screenshot_pending = true;
+            ctx.send_viewport_cmd(ViewportCommand::Screenshot(egui::UserData::default()));
+        }
+        if !self.screenshot_pending {
+            return;
+        }
+
+        let image = ctx.input(|i| {
+            i.raw.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+        let Some(image) = image else {
+            ctx.request_repaint();
+            return;
+        };
+        self.screenshot_pending = false;
+
+        let path = match (&self.screenshot_to, self.config.file()) {
+            (Some(path), _) => path.clone(),
+            (None, Some(file)) => screenshot_path(file),
+            (None, None) => {
+                warn!("Screenshots are saved next to the config file, and there is none.");
+                return;
+            }
+        };
+        match crop_to_content(&image) {
+            Some(image) => match save_png(&image, &path) {
+                Ok(()) => info!("Saved screenshot to {}", path.display()),
+                Err(e) => error!("Failed to save screenshot '{}': {}", path.display(), e),
+            },
+            None => warn!("Nothing on screen to save as a screenshot."),
+        }
+        if self.screenshot_to.is_some() {
+            ctx.send_viewport_cmd(ViewportCommand::Close);
+        }
+    }
+
+    /// Returns whether input should be shown right now, per `trigger` and `only_when_focused`.
+    fn triggered(&self) -> bool {
+        let held = match self.config.trigger {
+            Trigger::Always => true,
+            Trigger::ModifierHeld => !self.chords.held().is_empty(),
+        };
+        held && self.focus_matches()
+    }
+
+    /// Makes the overlay draggable while `grab_modifier` is held, per the modifiers held
+    /// after this frame's input.
+    ///
+    /// Where the overlay was dragged becomes its position, so a later reload or
+    /// `follow_cursor` starts from there instead of snapping back.
+    fn update_grab(&mut self, ctx: &Context) {
+        let grabbing = self
+            .config
+            .grab_modifier
+            .is_some_and(|modifier| self.chords.held().contains(&modifier.chord_name()));
+
+        // Check on the frame the modifier is let go too, since the drag ends before it
+        if self.dragged && (grabbing || self.grabbing) {
+            if let Some(rect) = ctx.input(|i| i.viewport().outer_rect) {
+                self.origin = [rect.min.x - self.offset[0], rect.min.y - self.offset[1]];
+            }
+        }
+
+        if grabbing != self.grabbing {
+            self.grabbing = grabbing;
+            ctx.send_viewport_cmd(ViewportCommand::MousePassthrough(self.mouse_passthrough()));
+            // Show or hide the grab outline right away
+            ctx.request_repaint();
+        }
+    }
+
+    /// Moves the overlay next to the cursor for `follow_cursor`, at most once per
+    /// `FOLLOW_INTERVAL` and only when the cursor has moved. The overlay is kept whole on
+    /// the monitor the cursor is on.
+    fn follow_cursor(&mut self, ctx: &Context) {
+        let elapsed = self.last_follow.elapsed();
+        if elapsed < FOLLOW_INTERVAL {
+            // Come back for the latest position, since the wake for it may have been skipped
+            ctx.request_repaint_after(FOLLOW_INTERVAL - elapsed);
+            return;
+        }
+        let Some(cursor) = cursor_position() else {
+            return;
+        };
+
+        // Keep `margin` away from the pointer so it doesn't cover the keys
+        let margin = self.config.margin;
+        let target = [cursor[0] + margin[0], cursor[1] + margin[1]];
+        let monitors = self.monitors.get_or_insert_with(detect_monitors);
+        let target = clamp_to_monitor(target, &self.config, monitors);
+        if target != self.origin {
+            self.origin = target;
+            self.last_follow = Instant::now();
+            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(self.position()));
+        }
+    }
+
+    /// Applies the next theme in `theme_cycle`, wrapping back to the first.
+    fn next_theme(&mut self) {
+        let count = self.config.theme_cycle.len();
+        if count == 0 {
+            return;
+        }
+        self.theme = Some(self.theme.map_or(0, |i| (i + 1) % count));
+        self.apply_theme();
+    }
+
+    /// Recolors the styles with the current `theme_cycle` theme, if one was picked.
+    fn apply_theme(&mut self) {
+        let theme = self
+            .theme
+            .and_then(|i| self.config.theme_cycle.get(i))
+            .and_then(|name| Theme::named(name));
+        if let Some(theme) = theme {
+            theme.apply(&mut self.config.styles);
+        }
+    }
+
+    /// Returns whether a key label falls in the `Unknown` category once formatted.
+    fn is_unknown(&self, label: &str) -> bool {
+        let (_, formatted) = format_label(label, false, &self.config);
+        category_for_key(&formatted) == KeyCategory::Unknown
+    }
+
+    /// Records an unknown key to `unmapped.log`, at most once per key per run.
+    ///
+    /// Nothing is written for an `in_memory` config, which has no folder for the log.
+    fn log_unmapped(&mut self, id: &KeyId, label: &str) {
+        if !self.unmapped.insert(id.clone()) {
+            return;
+        }
+        let Some(file) = self.config.file() else {
+            return;
+        };
+
+        let path = unmapped_log_path(file);
+        match log_unmapped(&path, id, label, self.layout) {
+            Ok(true) => info!("Logged unmapped key {} to {}", id.0, path.display()),
+            Ok(false) => {}
+            Err(e) => warn!("Failed to write '{}': {}", path.display(), e),
+        }
+    }
+
+    /// Returns whether the focused window is one listed in `only_when_focused`.
+    ///
+    /// Uses the window last seen by `watch_focus`, so no platform query runs while drawing.
+    /// When the focused window can't be detected, input is shown as usual.
+    fn focus_matches(&self) -> bool {
+        self.config.only_when_focused.is_empty()
+            || last_focused_window().is_none_or(|w| w.matches(&self.config.only_when_focused))
+    }
+
+    /// Feeds one input event to the overlay, exactly as if the input listener sent it.
+    ///
+    /// This lets an embedding app drive the overlay itself: construct the app with a
+    /// receiver nothing sends to (e.g. `VisualiserApp::new(config, mpsc::channel().1)`,
+    /// with a `Config::in_memory()` to skip the config file), push events as they happen,
+    /// and request a repaint.
+    ///
+    /// # Returns
+    /// `true` if the overlay changed and should be repainted.
+    pub fn push_event(&mut self, event: InputEvent) -> bool {
+        let debounce = Duration::from_millis(self.config.debounce_ms);
+        let mut changed = false;
+
+        // Releases only end chords; they never add keys
+        if let InputEvent::KeyRelease(id, label) = &event {
+            self.chords.release(id, label);
+            self.key_buffer.release(id);
+            if self.config.group_text {
+                self.debouncer.release(&id.0);
+            }
+
+            // With `trigger = "modifier_held"`, letting go of the last modifier hides everything
+            let hide =
+                self.config.trigger == Trigger::ModifierHeld && self.chords.held().is_empty();
+            if hide {
+                self.key_buffer.clear();
+                self.caption.clear();
+            }
+            return hide;
+        }
+
+        // In sticky mode, a press after the previous chord settled replaces it
+        if self.config.sticky {
+            self.key_buffer
+                .clear_if_settled(Duration::from_millis(self.config.timeout_ms));
+        }
+
+        match event {
+            InputEvent::KeyPress(id, label) => {
+                // The clear hotkey wipes the overlay and is not shown itself
+                let held = self.chords.held();
+                if let Some(hotkey) = &self.config.clear_hotkey {
+                    if hotkey.matches(&held, &label) {
+                        self.key_buffer.clear();
+                        self.caption.clear();
+                        self.numpad.clear();
+                        return true;
+                    }
+                }
+                if let Some(hotkey) = &self.config.theme_cycle_hotkey {
+                    if hotkey.matches(&held, &label) {
+                        self.next_theme();
+                        return true;
+                    }
+                }
+                if let Some(hotkey) = &self.config.screenshot_hotkey {
+                    if hotkey.matches(&held, &label) {
+                        request_screenshot();
+                        return true;
+                    }
+                }
+                if let Some(hotkey) = &self.config.settings_hotkey {
+                    if hotkey.matches(&held, &label) {
+                        request_settings();
+                        return true;
+                    }
+                }
+
+                let chord = self.chords.press(&id, &label);
+                let unknown = self.is_unknown(&label);
+                if unknown && self.config.log_unmapped {
+                    self.log_unmapped(&id, &label);
+                }
+                if !self.triggered() || (unknown && !self.config.show_unknown) {
+                    return false;
+                }
+
+                // Captions show every press, including auto-repeat, as the typed text
+                if self.config.display_mode == DisplayMode::Caption {
+                    self.caption.push_key(&id, &label, &self.config);
+                    return true;
+                }
+
+                // With `show_numpad_grid`, numpad keys light up their grid cell instead
+                if self.config.show_numpad_grid && NumpadGrid::contains(&id) {
+                    self.numpad.press(&id);
+                    return true;
+                }
+
+                let shown = if self.config.modifiers_only_in_chords {
+                    chord
+                } else {
+                    vec![(id, label)]
+                };
+
+                for (id, label) in shown {
+                    // Typed text debounces per physical key, so a re-press after release counts
+                    let repeat_key = if self.config.group_text {
+                        &id.0
+                    } else {
+                        &label
+                    };
+                    if self.debouncer.accept(repeat_key, Instant::now(), debounce) {
+                        // Velocity comes from the gap since the previous key that was shown
+                        let velocity = self
+                            .last_press
+                            .replace(Instant::now())
+                            .map_or(0.0, |last| press_velocity(last.elapsed()));
+                        self.key_buffer.push_key_with_velocity(
+                            Some(&id),
+                            &label,
+                            false,
+                            velocity,
+                            &self.config,
+                        );
+                        changed = true;
+                    }
+                }
+
+                // Keys that arrived together read as a shortcut: Ctrl+Shift+P, not P+Ctrl+Shift
+                if changed && self.config.sort_simultaneous == SortSimultaneous::ModifierFirst {
+                    self.key_buffer.order_modifiers_first(SIMULTANEOUS_WINDOW);
+                }
+            }
+            InputEvent::MouseClick(label) => {
+                // Drag indicators are opt-in, and clicks follow the trigger like keys
+                if (label == "󰍽 drag" && !self.config.show_mouse_drag)
+                    || self.config.display_mode == DisplayMode::Caption
+                    || !self.triggered()
+                {
+                    return false;
+                }
+
+                let label = self.debouncer.detect_double_click(
+                    label,
+                    Instant::now(),
+                    Duration::from_millis(self.config.double_click_ms),
+                );
+                if self.debouncer.accept(&label, Instant::now(), debounce) {
+                    // A double click takes over the box of the click that started it
+                    if label == "MouseDouble" {
+                        self.key_buffer.push_double_click(&self.config);
+                    } else {
+                        self.key_buffer.push_key(None, &label, true, &self.config);
+                    }
+                    changed = true;
+                }
+            }
+            InputEvent::KeyRelease(..) => {}
+        }
+
+        changed
+    }
+
+    /// Paints the keys (or caption) on screen into `ui`, filling its available space.
+    ///
+    /// The overlay window calls this every frame. An embedding app can call it from any
+    /// panel or window of its own, after feeding input with `push_event`; keep requesting
+    /// repaints while keys are shown, since they animate in and expire.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        let width = ui.available_width();
+        self.last_ui_width = width;
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(self.last_frame);
+        self.last_frame = now;
+
+        // Explain a dead listener rather than showing an empty overlay
+        if let Some(message) = listener_failure() {
+            paint_notice(ui, message);
+            if self.config.show_capture_indicator {
+                paint_capture_indicator(ui, false);
+            }
+            return;
+        }
+        match self.config.display_mode {
+            DisplayMode::Keys if self.config.show_numpad_grid => {
+                // The grid takes the left edge; the key row fills what is left
+                let grid_width = self.numpad.render(ui, &self.config);
+                let mut rect = ui.max_rect();
+                rect.min.x += grid_width;
+                let mut row = ui.new_child(egui::UiBuilder::new().max_rect(rect));
+                self.key_buffer
+                    .render(&mut row, &self.config, width - grid_width, elapsed);
+            }
+            DisplayMode::Keys => self.key_buffer.render(ui, &self.config, width, elapsed),
+            DisplayMode::Caption => self.caption.render(ui, &self.config, width),
+        }
+
+        if self.grabbing {
+            let response = ui.interact(ui.max_rect(), ui.id().with("grab"), egui::Sense::drag());
+            if response.drag_started() {
+                ui.ctx().send_viewport_cmd(ViewportCommand::StartDrag);
+                self.dragged = true;
+            }
+            paint_grab_outline(ui);
+        }
+
+        if self.config.show_capture_indicator {
+            paint_capture_indicator(ui, self.focus_matches());
+        }
+
+        // Diagnostic: show which keyboard layout symbols are resolved with
+        if let Some(layout) = self.layout.filter(|_| self.config.show_layout) {
+            ui.painter().text(
+                ui.max_rect().left_top(),
+                egui::Align2::LEFT_TOP,
+                format!("layout: {layout}"),
+                egui::FontId::monospace(12.0),
+                Color32::from_white_alpha(180),
+            );
+        }
+    }
+
+    /// Updates the application state and renders one frame into the given viewport context.
+    fn draw(&mut self, ctx: &Context) {
+        // Reload config if the file has changed on disk; mirrors get theirs from the main overlay
+        let placement_before = placement(&self.config);
+        let reloaded = match self.mirror {
+            true => std::mem::take(&mut self.config_changed),
+            false => self.config.maybe_reload(),
+        };
+        if reloaded {
+            // Keep the theme picked with `theme_cycle_hotkey` over the reloaded styles
+            self.apply_theme();
+
+            // Recompute anchors/monitor-relative positions against the current monitors, unless
+            // the overlay was dragged somewhere else and its placement wasn't edited
+            if !self.dragged || placement(&self.config) != placement_before {
+                self.origin = resolve_clamped_position(&self.config);
+                self.dragged = false;
+            }
+            self.monitors = None;
+
+            // Reapply size, position, window flags, focus, and mouse passthrough
+            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(self.position()));
             ctx.send_viewport_cmd(ViewportCommand::InnerSize(egui::vec2(
                 self.config.size[0],
                 self.config.size[1],
             )));
+            ctx.send_viewport_cmd(ViewportCommand::WindowLevel(self.config.window_level()));
+            ctx.send_viewport_cmd(ViewportCommand::Decorations(self.config.decorations));
+            ctx.send_viewport_cmd(ViewportCommand::Resizable(self.config.resizable));
             ctx.send_viewport_cmd(ViewportCommand::Focus);
-            ctx.send_viewport_cmd(ViewportCommand::MousePassthrough(true));
+            ctx.send_viewport_cmd(ViewportCommand::MousePassthrough(self.mouse_passthrough()));
+
+            if !self.mirror {
+                self.update_mirrors(ctx);
+            }
+        }
+
+        // Poll the focused window in the background once `only_when_focused` needs it
+        if !self.config.only_when_focused.is_empty() {
+            let ctx = ctx.clone();
+            watch_focus(move || ctx.request_repaint());
         }
 
         let mut needs_repaint = false;
 
+        set_wake_on_cursor(self.config.follow_cursor);
+        if self.config.follow_cursor {
+            self.follow_cursor(ctx);
+        }
+
+        // Apply "clear all" requests made outside the input stream
+        if self.clears_handled != clear_requests() {
+            self.clears_handled = clear_requests();
+            self.key_buffer.clear();
+            self.caption.clear();
+            self.numpad.clear();
+            needs_repaint = true;
+        }
+        let debounce = Duration::from_millis(self.config.debounce_ms);
+
         // Handle all available input events from the background listener
         while let Ok(event) = self.rx.try_recv() {
-            match event {
-                InputEvent::KeyPress(label) => {
-                    if !self.recently_seen.contains(&label) {
-                        self.key_buffer.push_key("", &label, false);
-                        self.recently_seen.insert(label);
-                        needs_repaint = true;
-                    }
-                }
-                InputEvent::MouseClick(label) => {
-                    if !self.recently_seen.contains(&label) {
-                        self.key_buffer.push_key("", &label, true);
-                        self.recently_seen.insert(label);
-                        needs_repaint = true;
-                    }
-                }
-            }
+            needs_repaint |= self.push_event(event);
         }
 
-        // Debounce key repeat events every 250ms
-        if self.last_clear.elapsed() > Duration::from_millis(250) {
-            self.recently_seen.clear();
-            self.last_clear = Instant::now();
+        // Stop letting clicks through while `grab_modifier` is held, so the overlay can be dragged
+        self.update_grab(ctx);
+
+        // Drop debounce entries for keys that can repeat again
+        self.debouncer.prune(Instant::now(), debounce);
+
+        if self.preview {
+            self.key_buffer.fill_preview(&self.config);
         }
 
-        // Draw the transparent central panel with all active keys
+        // Draw the transparent central panel with all active keys; `show` moves `last_frame`
+        let since_last = self.last_frame.elapsed();
         CentralPanel::default()
             .frame(Frame::NONE.fill(Color32::TRANSPARENT))
-            .show(ctx, |ui| {
-                let width = ui.available_width();
-                self.last_ui_width = width;
-                self.key_buffer.render(ui, &self.config, width);
-            });
+            .show(ctx, |ui| self.show(ui));
+
+        // Request immediate repaint if we received an event; otherwise throttle.
+        // With `max_fps`, event repaints are coalesced so they never exceed the cap.
+        let min_frame = match self.config.max_fps {
+            0 => Duration::ZERO,
+            fps => Duration::from_secs_f64(1.0 / fps as f64),
+        };
+        frame_drawn(min_frame); // Lets event wakes wait for the rest of this frame too
 
-        // Request immediate repaint if we received an event; otherwise throttle
         if needs_repaint {
-            ctx.request_repaint();
+            ctx.request_repaint_after(min_frame.saturating_sub(since_last));
+        } else if self.config.render_mode == RenderMode::Reactive {
+            // Only while something still moves; otherwise sleep until new input wakes the UI
+            if self.key_buffer.is_animating(&self.config)
+                || !self.caption.is_empty()
+                || self.numpad.is_lit()
+            {
+                ctx.request_repaint_after(Duration::from_millis(33).max(min_frame));
+            }
+        } else if !self.key_buffer.is_empty() || !self.caption.is_empty() || self.numpad.is_lit() {
+            // ~30fps refresh while keys are animating/expiring, or slower if the cap is lower
+            ctx.request_repaint_after(Duration::from_millis(33).max(min_frame));
         } else {
-            ctx.request_repaint_after(Duration::from_millis(33)); // ~30fps idle refresh
+            // Nothing on screen: new input wakes the UI, so only poll slowly for config reloads
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+}
+
+impl App for VisualiserApp {
+    /// Called every frame to update the application state and render the UI.
+    fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+        let blur_background = self.config.blur_background;
+        self.show_mirrors(ctx);
+        self.show_settings(ctx);
+        self.draw(ctx);
+        self.take_screenshot(ctx);
+
+        // Apply `blur_background` when a reload turns it on or off
+        if self.config.blur_background != blur_background {
+            blur_behind(frame, self.config.blur_background);
         }
     }
 
@@ -105,3 +737,112 @@ impl App for VisualiserApp {
         Rgba::TRANSPARENT.to_array()
     }
 }
+
+/// Paints `message` wrapped in a dark box filling the overlay, for problems the user must fix.
+fn paint_notice(ui: &egui::Ui, message: &str) {
+    let rect = ui.max_rect().shrink(4.0);
+    let galley = ui.painter().layout(
+        message.to_string(),
+        egui::FontId::proportional(14.0),
+        Color32::WHITE,
+        rect.width() - 16.0,
+    );
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(
+        rect,
+        egui::CornerRadius::same(8),
+        Color32::from_rgb(120, 30, 30),
+    );
+    painter.galley(
+        rect.left_top() + egui::vec2(8.0, 8.0),
+        galley,
+        Color32::WHITE,
+    );
+}
+
+/// Paints the `show_capture_indicator` dot in the top-right corner: red while input is
+/// captured, grey while `only_when_focused` has paused it or the listener failed.
+fn paint_capture_indicator(ui: &egui::Ui, capturing: bool) {
+    const RADIUS: f32 = 4.0;
+    const INSET: f32 = 6.0;
+
+    let color = if capturing {
+        Color32::from_rgb(220, 40, 40)
+    } else {
+        Color32::from_gray(140)
+    };
+    let center = ui.max_rect().right_top() + egui::vec2(-INSET - RADIUS, INSET + RADIUS);
+    ui.painter().circle_filled(center, RADIUS, color);
+}
+
+/// Returns the settings that decide where the overlay is placed, to tell whether a reload
+/// changed them.
+fn placement(config: &Config) -> impl PartialEq {
+    (
+        config.position,
+        config.position_relative_to,
+        config.anchor,
+        config.monitor,
+        config.margin,
+        config.size,
+    )
+}
+
+/// Outlines the overlay while `grab_modifier` is held, to show it can be dragged.
+fn paint_grab_outline(ui: &egui::Ui) {
+    ui.painter().rect_stroke(
+        ui.max_rect().shrink(1.0),
+        egui::CornerRadius::same(4),
+        egui::Stroke::new(2.0, Color32::from_white_alpha(160)),
+        egui::StrokeKind::Inside,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn pushed_key_presses_are_shown() {
+        let mut app = VisualiserApp::new(Config::in_memory(), mpsc::channel().1);
+        assert!(app.push_event(InputEvent::KeyPress(KeyId("KeyA".into()), "A".into())));
+        assert!(!app.push_event(InputEvent::KeyRelease(KeyId("KeyA".into()), "A".into())));
+
+        let labels: Vec<&str> = app
+            .key_buffer()
+            .keys
+            .iter()
+            .map(|k| k.label.as_str())
+            .collect();
+        assert_eq!(labels, ["A"]);
+    }
+
+    #[test]
+    fn double_click_replaces_the_left_click() {
+        // Without debouncing, so quick repeat clicks all get through
+        let config = Config {
+            debounce_ms: 0,
+            ..Config::default()
+        };
+        let mut app = VisualiserApp::new(config, mpsc::channel().1);
+        let click = || InputEvent::MouseClick("MouseLeft".into());
+        let shown = |app: &VisualiserApp| -> Vec<String> {
+            app.key_buffer
+                .keys
+                .iter()
+                .map(|k| format!("{} {}", k.icon, k.label))
+                .collect()
+        };
+
+        assert!(app.push_event(click()));
+        assert!(app.push_event(click()));
+        assert_eq!(shown(&app), ["󰍽 double"]);
+
+        // A third click starts over; a fourth makes a double again, still shown once
+        assert!(app.push_event(click()));
+        assert!(app.push_event(click()));
+        assert_eq!(shown(&app), ["󰍽 double"]);
+    }
+}